@@ -1,6 +1,6 @@
 use crate::{
     app::components::{footer::Footer, navbar::Navbar},
-    error_template::{AppError, ErrorTemplate},
+    error_template::NotFound,
 };
 use leptos::*;
 use leptos_meta::*;
@@ -25,26 +25,27 @@ pub fn App() -> impl IntoView {
         <Title text="Gov Data + Leptos + SurrealDB"/>
 
         // content for this welcome page
-        <Router fallback=|| {
-            let mut outside_errors = Errors::default();
-            outside_errors.insert_with_default_key(AppError::NotFound);
-            view! {
-                <ErrorTemplate outside_errors/>
-            }
-            .into_view()
-        }>
+        <Router fallback=|| view! { <NotFound/> }.into_view()>
             <main class="flex flex-col h-screen bg-slate-100">
                 <Navbar/>
                 <div class="flex-1">
                     <Routes>
                         <Route path="" view=routes::home::HomePage/>
                         <Route path="/bills" view=routes::bills::BillsPage/>
-                        <Route path="/bills/:bill_type/:bill_number" view=routes::bills::bill::Bill/>
+                        <Route path="/bills/:congress/:bill_type/:bill_number" view=routes::bills::bill::Bill/>
+                        <Route path="/bills/:congress/:bill_type/:bill_number/text/:version" view=routes::bills::text::BillText/>
+                        <Route path="/bills/:congress/:bill_type/:bill_number/compare/:from/:to" view=routes::bills::compare::BillTextCompare/>
+                        <Route path="/bills/:bill_type/:bill_number" view=routes::bills::bill::CurrentCongressBill/>
                         <Route path="/feeds" view=routes::feeds::Feeds>
                             <Route path="" view=routes::feeds::main::FeedsPage/>
-                            <Route path="/bills" view=routes::feeds::bills::BillsFeed/>
+                            <Route path="/bills" view=routes::feeds::bills::BillsFeed ssr=SsrMode::Async/>
+                            <Route path="/:collection" view=routes::feeds::collection::CollectionFeed/>
                         </Route>
+                        <Route path="/members" view=routes::members::MembersPage/>
+                        <Route path="/members/:bioguide_id" view=routes::members::member::MemberPage/>
+                        <Route path="/search" view=routes::search::SearchPage/>
                         <Route path="/maps" view=routes::maps::MapPage/>
+                        <Route path="/admin/feeds" view=routes::admin::FeedPolling/>
                     </Routes>
                 </div>
                 <Footer/>
@@ -0,0 +1,19 @@
+use leptos::*;
+
+use crate::domain::api_key::is_api_key_missing;
+
+/// Shows a server function error, with a notice instead of the raw error when the API key is missing.
+#[component]
+pub fn ServerErrorMessage(error: ServerFnError) -> impl IntoView {
+    if is_api_key_missing(&error) {
+        view! {
+            <div class="m-4 p-4 text-sm text-yellow-800 rounded-lg bg-yellow-50 dark:bg-gray-800 dark:text-yellow-300" role="alert">
+                <p class="font-medium">"API key not configured"</p>
+                <p>"Set CONGRESS_GOV_API_TOKEN to load data from Congress.gov."</p>
+            </div>
+        }
+        .into_view()
+    } else {
+        view! { <pre class="error">"Server Error: " {error.to_string()}</pre> }.into_view()
+    }
+}
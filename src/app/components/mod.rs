@@ -1,2 +1,4 @@
+pub mod api_key;
 pub mod footer;
 pub mod navbar;
+pub mod stage_progress;
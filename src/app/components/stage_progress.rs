@@ -0,0 +1,30 @@
+use leptos::*;
+
+use crate::domain::bills::LegislativeStage;
+
+/// One segment per stage, filled up to and including `stage`.
+#[component]
+pub fn StageProgress(stage: LegislativeStage) -> impl IntoView {
+    let segments = LegislativeStage::ALL
+        .into_iter()
+        .map(|step| {
+            let class = if step <= stage {
+                "h-2 flex-1 rounded bg-blue-600 dark:bg-blue-500"
+            } else {
+                "h-2 flex-1 rounded bg-gray-200 dark:bg-gray-700"
+            };
+            view! { <div class=class title=step.to_string()></div> }
+        })
+        .collect_view();
+
+    view! {
+        <div class="mb-3">
+            <div class="flex gap-1" role="progressbar" aria-valuemin="1" aria-valuemax=LegislativeStage::ALL.len() aria-valuenow=stage.step()>
+                {segments}
+            </div>
+            <p class="mt-1 text-xs text-gray-500 dark:text-gray-400">
+                {stage}" ("{stage.step()}" of "{LegislativeStage::ALL.len()}")"
+            </p>
+        </div>
+    }
+}
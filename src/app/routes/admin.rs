@@ -0,0 +1,157 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::feeds::polling::{FeedFetch, FeedSource};
+use crate::domain::feeds::Collection;
+
+/// State of the feed poller at `/admin/feeds`: when each feed is polled next and the latest fetches.
+#[component]
+pub fn FeedPolling() -> impl IntoView {
+    let polling = create_resource(|| (), |_| async move { get_feed_polling().await });
+
+    view! {
+        <div class="bg-slate-100">
+            <h1>"Feed Polling"</h1>
+            <Transition fallback=move || view! { <p>"Loading..."</p> }>
+                {move || polling.get().map(|polling| match polling {
+                    Err(error) => view! { <p>"Error loading polling state: "{error.to_string()}</p> }.into_view(),
+                    Ok(polling) => view! {
+                        <SourceTable sources=polling.sources/>
+                        <FetchTable fetches=polling.fetches/>
+                    }.into_view(),
+                })}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn SourceTable(sources: Vec<SourceState>) -> impl IntoView {
+    view! {
+        <table class="m-4 text-sm text-left text-gray-700 bg-white rounded-lg shadow">
+            <thead class="text-xs uppercase bg-gray-50">
+                <tr>
+                    <th class="px-4 py-2">"Feed"</th>
+                    <th class="px-4 py-2">"ETag"</th>
+                    <th class="px-4 py-2">"Last-Modified"</th>
+                    <th class="px-4 py-2">"TTL"</th>
+                    <th class="px-4 py-2">"Failures"</th>
+                    <th class="px-4 py-2">"Next poll"</th>
+                </tr>
+            </thead>
+            <tbody>
+                {sources
+                    .into_iter()
+                    .map(|state| {
+                        let name = state.collection.to_string();
+                        match state.source {
+                            None => view! {
+                                <tr class="border-t">
+                                    <td class="px-4 py-2 font-medium">{name}</td>
+                                    <td class="px-4 py-2 text-gray-500" colspan="5">"Not polled yet"</td>
+                                </tr>
+                            },
+                            Some(source) => view! {
+                                <tr class="border-t">
+                                    <td class="px-4 py-2 font-medium">{name}</td>
+                                    <td class="px-4 py-2 font-mono">{source.etag}</td>
+                                    <td class="px-4 py-2">{source.last_modified}</td>
+                                    <td class="px-4 py-2">{source.ttl_minutes.map(|ttl| format!("{ttl} min"))}</td>
+                                    <td class="px-4 py-2">{source.failures}</td>
+                                    <td class="px-4 py-2">{source.next_poll.0.format("%Y-%m-%d %H:%M UTC").to_string()}</td>
+                                </tr>
+                            },
+                        }
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+    }
+}
+
+#[component]
+fn FetchTable(fetches: Vec<FeedFetch>) -> impl IntoView {
+    if fetches.is_empty() {
+        return view! { <p class="m-4">"No feeds were fetched yet."</p> }.into_view();
+    }
+
+    view! {
+        <table class="m-4 text-sm text-left text-gray-700 bg-white rounded-lg shadow">
+            <thead class="text-xs uppercase bg-gray-50">
+                <tr>
+                    <th class="px-4 py-2">"Fetched"</th>
+                    <th class="px-4 py-2">"Feed"</th>
+                    <th class="px-4 py-2">"Status"</th>
+                    <th class="px-4 py-2">"Bytes"</th>
+                    <th class="px-4 py-2">"Items"</th>
+                    <th class="px-4 py-2">"Rejected"</th>
+                    <th class="px-4 py-2">"Time"</th>
+                    <th class="px-4 py-2">"Error"</th>
+                </tr>
+            </thead>
+            <tbody>
+                {fetches
+                    .into_iter()
+                    .map(|fetch| {
+                        let class = if fetch.error.is_some() {
+                            "border-t bg-red-50"
+                        } else if fetch.not_modified() {
+                            "border-t text-gray-500"
+                        } else {
+                            "border-t"
+                        };
+                        view! {
+                            <tr class=class>
+                                <td class="px-4 py-2">{fetch.fetched_at.0.format("%Y-%m-%d %H:%M:%S").to_string()}</td>
+                                <td class="px-4 py-2">{fetch.source}</td>
+                                <td class="px-4 py-2">{fetch.status.map_or("-".to_string(), |status| status.to_string())}</td>
+                                <td class="px-4 py-2">{fetch.bytes}</td>
+                                <td class="px-4 py-2">{fetch.items}</td>
+                                <td class="px-4 py-2">{fetch.rejected}</td>
+                                <td class="px-4 py-2">{fetch.duration_ms}" ms"</td>
+                                <td class="px-4 py-2 text-red-700">{fetch.error}</td>
+                            </tr>
+                        }
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+    }
+    .into_view()
+}
+
+/// Fetches shown on the page, newest first.
+#[cfg(feature = "ssr")]
+const FETCH_LIMIT: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SourceState {
+    collection: Collection,
+    /// `None` until the poller first reached the feed.
+    source: Option<FeedSource>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PollingState {
+    sources: Vec<SourceState>,
+    fetches: Vec<FeedFetch>,
+}
+
+#[server]
+async fn get_feed_polling() -> Result<PollingState, ServerFnError> {
+    use crate::db::db;
+
+    let db = db()?;
+    let mut sources = Vec::new();
+    for collection in Collection::ALL {
+        let source: Option<FeedSource> = db.select(("feed_source", collection.slug())).await?;
+        sources.push(SourceState { collection, source });
+    }
+    let fetches: Vec<FeedFetch> = db
+        .query("SELECT * FROM feed_fetch ORDER BY fetched_at DESC LIMIT $limit")
+        .bind(("limit", FETCH_LIMIT))
+        .await?
+        .take(0)?;
+
+    Ok(PollingState { sources, fetches })
+}
@@ -1,76 +1,169 @@
 use leptos::*;
-use leptos_router::ActionForm;
+use leptos_router::{use_query, Form, Params, ParamsError, ParamsMap, A};
 use serde::{Deserialize, Serialize};
 
+use crate::app::components::api_key::ServerErrorMessage;
+use crate::domain::bills::{ordinal, BillListItem as Bill, BillListQuery, BillSort, BillType};
+use crate::domain::sync::{SyncState, SyncStatus};
 use crate::error_template::ErrorTemplate;
 
 pub mod bill;
+pub mod compare;
+pub mod text;
 
+/// Bill list at `/bills`. Filters, sort and page live in the query string, so links
+/// and back/forward navigation restore the same view.
 #[component]
 pub fn BillsPage() -> impl IntoView {
-    let get_bills_action = create_server_action::<GetBills>();
-
-    let bills = get_bills_action.value();
+    let query = use_query::<BillListQuery>();
+    let query = move || query.get().unwrap_or_default();
+    let bills = create_resource(query, |query| async move { get_bills(query).await });
 
     view! {
         <div class="bg-slate-100">
             <h1>"Bills Page"</h1>
-            <ActionForm action=get_bills_action>
-                <input type="submit" value="Get Bills"/>
-            </ActionForm>
+            <BillFilters query=Signal::derive(query)/>
             <div>
             <Transition fallback=move || view! { <p>"Loading..."</p> }>
                 <ErrorBoundary fallback=|errors| {
                     view! { <ErrorTemplate errors=errors/> }
                 }>
                     {move || {
-                        let existing_bills = {
-                            move || {
-                               bills
-                                    .get()
-                                    .map(move |bills| match bills {
-                                        Err(e) => {
-                                            view! {
-                                                <pre class="error">"Server Error: " {e.to_string()}</pre>
-                                            }
-                                                .into_view()
-                                        }
-                                        Ok(bills) => {
-                                            if bills.bills.is_empty() {
-                                                view! { <p>"No bills were found."</p> }.into_view()
-                                            } else {
-                                               bills.bills
-                                                    .into_iter()
-                                                    .map(move |bill| {
-                                                        view! {
-                                                            <li>
-                                                                <BillCard bill/>
-                                                            </li>
-                                                        }
-                                                    })
-                                                    .collect_view()
-                                            }
+                        bills
+                            .get()
+                            .map(move |bills| match bills {
+                                Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                                Ok(bills) => {
+                                    let sync = bills.sync.map(|status| view! { <li><SyncStatusLine status/></li> });
+                                    if bills.bills.is_empty() {
+                                        view! { <ul>{sync}</ul> <p>"No bills were found."</p> }.into_view()
+                                    } else {
+                                        let cards = bills.bills
+                                            .into_iter()
+                                            .map(move |bill| view! { <li><BillCard bill/></li> })
+                                            .collect_view();
+                                        view! {
+                                            <ul>{sync} {cards}</ul>
+                                            <Pagination query=query() total=bills.total/>
                                         }
-                                    })
-                                    .unwrap_or_default()
-                            }
-                        };
-                    view! { <ul>{existing_bills}</ul> }
+                                        .into_view()
+                                    }
+                                }
+                            })
+                            .unwrap_or_default()
                     }}
-                   </ErrorBoundary>
+                </ErrorBoundary>
             </Transition>
             </div>
         </div>
     }
 }
 
+/// Submits as a GET to `/bills`, which resets the page.
+#[component]
+fn BillFilters(#[prop(into)] query: Signal<BillListQuery>) -> impl IntoView {
+    let congress = move || {
+        query
+            .get()
+            .congress
+            .map(|congress| congress.to_string())
+            .unwrap_or_default()
+    };
+    let bill_type = move || {
+        query
+            .get()
+            .bill_type
+            .map(|bill_type| bill_type.slug())
+            .unwrap_or_default()
+    };
+    let sort = move || query.get().sort.param();
+
+    view! {
+        <Form method="GET" action="/bills" class="flex flex-wrap items-end gap-4 m-4">
+            <label class="text-sm text-gray-700">
+                "Congress"
+                <input type="number" name="congress" min="1" placeholder="Any"
+                    class="block w-24 p-2 border border-gray-300 rounded-lg"
+                    value=congress prop:value=congress/>
+            </label>
+            <label class="text-sm text-gray-700">
+                "Type"
+                <select name="type" class="block p-2 border border-gray-300 rounded-lg" prop:value=bill_type>
+                    <option value="" selected=move || bill_type().is_empty()>"Any"</option>
+                    {BillType::ALL
+                        .into_iter()
+                        .map(|option| view! {
+                            <option value=option.slug() selected=move || bill_type() == option.slug()>
+                                {option.citation()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <label class="text-sm text-gray-700">
+                "Sort"
+                <select name="sort" class="block p-2 border border-gray-300 rounded-lg" prop:value=sort>
+                    {BillSort::ALL
+                        .into_iter()
+                        .map(|option| view! {
+                            <option value=option.param() selected=move || sort() == option.param()>
+                                {option.label()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <input type="submit" value="Filter" class="px-3 py-2 text-sm font-medium text-white bg-blue-700 rounded-lg hover:bg-blue-800"/>
+        </Form>
+    }
+}
+
+#[component]
+fn Pagination(query: BillListQuery, total: u32) -> impl IntoView {
+    let pages = BillListQuery::pages(total);
+    let page = query.page.min(pages);
+    let href = move |page| format!("/bills{}", query.with_page(page).to_query_string());
+    let class = "px-3 py-2 text-sm font-medium text-gray-900 bg-white border border-gray-200 rounded-lg hover:bg-gray-100";
+
+    view! {
+        <nav class="flex items-center gap-4 m-4">
+            {(page > 1).then(|| view! { <A href=href(page - 1) class>"Previous"</A> })}
+            <span class="text-sm text-gray-700">"Page "{page}" of "{pages}" ("{total}" bills)"</span>
+            {(page < pages).then(|| view! { <A href=href(page + 1) class>"Next"</A> })}
+        </nav>
+    }
+}
+
+/// When the local bill table was last synced from Congress.gov.
+#[component]
+fn SyncStatusLine(status: SyncStatus) -> impl IntoView {
+    let finished = status
+        .finished_at
+        .map(|finished_at| finished_at.0.format("%Y-%m-%d %H:%M UTC").to_string());
+    match status.state {
+        SyncState::Running => {
+            view! { <p class="m-4 text-sm text-gray-500">"Syncing bills from Congress.gov..."</p> }
+        }
+        SyncState::Ok => view! {
+            <p class="m-4 text-sm text-gray-500">
+                "Synced "{status.synced}" bill(s) at "{finished.unwrap_or_default()}
+            </p>
+        },
+        SyncState::Failed => view! {
+            <p class="m-4 text-sm text-red-700">
+                "Last sync failed at "{finished.unwrap_or_default()}": "{status.error.unwrap_or_default()}
+            </p>
+        },
+    }
+}
+
 #[component]
 fn BillCard(bill: Bill) -> impl IntoView {
     view! {
        <div class="max-w-sm m-4 p-6 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
-            <h5 class="mb-2 text-2xl font-bold tracking-tight text-gray-900 dark:text-white">{&bill.bill_type}" "{&bill.number}</h5>
+            <h5 class="mb-2 text-2xl font-bold tracking-tight text-gray-900 dark:text-white">{bill.bill_type}" "{&bill.number}" ("{ordinal(bill.congress)}")"</h5>
             <p class="mb-3 font-normal text-gray-700 dark:text-gray-400">{bill.title}</p>
-            <a href={format!("bills/{}/{}", bill.bill_type, bill.number)} class="inline-flex items-center px-3 py-2 text-sm font-medium text-center text-white bg-blue-700 rounded-lg hover:bg-blue-800 focus:ring-4 focus:outline-none focus:ring-blue-300 dark:bg-blue-600 dark:hover:bg-blue-700 dark:focus:ring-blue-800">
+            <a href={format!("/bills/{}/{}/{}", bill.congress, bill.bill_type.slug(), bill.number)} class="inline-flex items-center px-3 py-2 text-sm font-medium text-center text-white bg-blue-700 rounded-lg hover:bg-blue-800 focus:ring-4 focus:outline-none focus:ring-blue-300 dark:bg-blue-600 dark:hover:bg-blue-700 dark:focus:ring-blue-800">
             "Get Details"
                <svg class="rtl:rotate-180 w-3.5 h-3.5 ms-2" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 14 10">
                    <path stroke="currentColor" stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M1 5h12m0 0L9 1m4 4L9 9"/>
@@ -80,38 +173,98 @@ fn BillCard(bill: Bill) -> impl IntoView {
     }
 }
 
+/// Both filters of the local bill table, matching everything for a `NONE` parameter.
+#[cfg(feature = "ssr")]
+const BILL_FILTER: &str =
+    "($congress = NONE OR congress = $congress) AND ($type = NONE OR type = $type)";
+
+/// One page of bills from Congress.gov, whose pagination gives the total. Without an API key,
+/// or when the API fails, the page is read from the local `bill` table instead.
 #[server]
-async fn get_bills() -> Result<Bills, ServerFnError> {
-    let client = reqwest::Client::new();
+async fn get_bills(query: BillListQuery) -> Result<Bills, ServerFnError> {
+    use crate::congress::{client, BillQuery};
+    use crate::db::db;
+    use crate::domain::bills::current_congress;
+    use crate::ingest;
+    use leptos::logging::warn;
 
-    let token = dotenvy_macro::dotenv!("CONGRESS_GOV_API_TOKEN");
+    let db = db()?;
+    let sync = ingest::status(&db, ingest::BILLS).await?;
 
-    let bills = client
-        .get(format!(
-            "https://api.congress.gov/v3/bill?format=json&offset=0&limit=10&api_key={}",
-            token
-        ))
-        .send()
-        .await?
-        .text()
-        .await?;
+    // Congress.gov can only filter by type within a Congress.
+    let congress = query
+        .congress
+        .or(query.bill_type.map(|_| current_congress()));
 
-    let bills: Bills = serde_json::from_str(bills.as_str())?;
+    let api_error = match client() {
+        Ok(client) => {
+            let page = client
+                .bills(&BillQuery {
+                    congress,
+                    bill_type: query
+                        .bill_type
+                        .map(|bill_type| bill_type.slug().to_string()),
+                    offset: query.offset(),
+                    limit: BillListQuery::PAGE_SIZE,
+                    sort: Some(query.sort.api().to_string()),
+                    ..Default::default()
+                })
+                .await;
+            match page {
+                Ok(page) => {
+                    return Ok(Bills {
+                        bills: page.items,
+                        total: page.count,
+                        sync,
+                    })
+                }
+                Err(error) => {
+                    warn!("listing bills from Congress.gov failed, reading stored bills: {error}");
+                    Some(error)
+                }
+            }
+        }
+        Err(_) => None,
+    };
 
-    dbg!("{:?}", &bills);
+    let order = match query.sort {
+        BillSort::Newest => "DESC",
+        BillSort::Oldest => "ASC",
+    };
+    let mut response = db
+        .query(format!(
+            "SELECT * FROM bill WHERE {BILL_FILTER} ORDER BY updateDate {order} LIMIT $limit START $start"
+        ))
+        .query(format!("SELECT count() AS total FROM bill WHERE {BILL_FILTER} GROUP ALL"))
+        .bind(("congress", congress))
+        .bind(("type", query.bill_type))
+        .bind(("limit", BillListQuery::PAGE_SIZE))
+        .bind(("start", query.offset()))
+        .await?;
+    let bills: Vec<Bill> = response.take(0)?;
+    let total: Option<u32> = response.take((1, "total"))?;
 
-    Ok(bills)
+    match (total, api_error) {
+        (None | Some(0), Some(error)) => Err(error.into()),
+        (total, _) => Ok(Bills {
+            bills,
+            total: total.unwrap_or_default(),
+            sync,
+        }),
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Bills {
     bills: Vec<Bill>,
+    /// Bills matching the filters across all pages.
+    total: u32,
+    /// Last run of the background sync, `None` before it ran.
+    sync: Option<SyncStatus>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Bill {
-    number: String,
-    title: String,
-    #[serde(rename = "type")]
-    bill_type: String,
+impl Params for BillListQuery {
+    fn from_map(map: &ParamsMap) -> Result<Self, ParamsError> {
+        Ok(Self::from_params(|key| map.get(key).map(String::as_str)))
+    }
 }
@@ -1,16 +1,33 @@
 use leptos::*;
 use leptos_router::use_params;
 use leptos_router::Params;
-use serde::{Deserialize, Serialize};
+use leptos_router::Redirect;
+
+use crate::app::components::api_key::ServerErrorMessage;
+use crate::app::components::stage_progress::StageProgress;
+use crate::app::routes::bills::text::{get_bill_text_versions, TextVersionList};
+use crate::domain::api_key::is_api_key_missing;
+use crate::domain::bills::{
+    ordinal, BillAction, BillCommittee, BillDetail, BillId, BillSubjects, BillType, Chamber,
+    Cosponsor, LegislativeStage, RelatedBill,
+};
+use crate::domain::graph::Bipartisanship;
+use crate::domain::members::member_url;
+use crate::error_template::NotFound;
 
 #[component]
 pub fn Bill() -> impl IntoView {
     let params = use_params::<BillParams>();
 
-    let bill = create_resource(
-        move || params.get().unwrap(),
-        |bill_params| async move { get_bill(bill_params.bill_type, bill_params.bill_number).await },
-    );
+    move || match params.get() {
+        Ok(params) => view! { <BillPage id=params.id()/> }.into_view(),
+        Err(_) => view! { <NotFound/> }.into_view(),
+    }
+}
+
+#[component]
+fn BillPage(id: BillId) -> impl IntoView {
+    let bill = create_resource(move || id, get_bill);
 
     view! {
         <div class="bg-slate-100">
@@ -21,56 +38,502 @@ pub fn Bill() -> impl IntoView {
                         }.into_view()
                     }
                     Some(bill) => match bill {
+                        Err(error) if is_api_key_missing(&error) => view! { <ServerErrorMessage error/> }.into_view(),
                         Err(e) => view!{ <h1>"Error loading bill: "{e.to_string()}</h1> }.into_view(),
-                        Ok(bill) => view! {
-                            <h1>"Specific Bill"</h1>
-                            <h1>{bill.title}</h1>
-                            }.into_view()
+                        Ok(bill) => view! { <BillOverview bill/> }.into_view()
                     }}}
             </Transition>
+            <TextVersionsSection id/>
+            <ActionsTimeline id/>
+            <CosponsorsSection id/>
+            <CommitteesSection id/>
+            <SubjectsSection id/>
+            <RelatedBillsSection id/>
+        </div>
+    }
+}
+
+#[component]
+pub(crate) fn Section(title: &'static str, children: Children) -> impl IntoView {
+    view! {
+        <section class="m-4 p-6 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
+            <h2 class="mb-2 text-xl font-bold tracking-tight text-gray-900 dark:text-white">{title}</h2>
+            {children()}
+        </section>
+    }
+}
+
+#[component]
+pub(crate) fn Field(label: &'static str, children: Children) -> impl IntoView {
+    view! {
+        <div class="mb-2">
+            <dt class="text-sm text-gray-500 dark:text-gray-400">{label}</dt>
+            <dd class="text-gray-900 dark:text-white">{children()}</dd>
         </div>
     }
 }
 
+#[component]
+fn BillOverview(bill: BillDetail) -> impl IntoView {
+    let BillDetail {
+        congress,
+        number,
+        title,
+        bill_type,
+        introduced_date,
+        origin_chamber,
+        latest_action,
+        policy_area,
+        sponsors,
+        cbo_cost_estimates,
+        committee_reports,
+        laws,
+        ..
+    } = bill;
+
+    view! {
+        <Section title="Overview">
+            <h1 class="mb-1 text-2xl font-bold text-gray-900 dark:text-white">{bill_type}" "{number}" ("{ordinal(congress)}" Congress)"</h1>
+            <p class="mb-4 text-gray-700 dark:text-gray-400">{title}</p>
+            <dl>
+                <Field label="Sponsor">
+                    {sponsors
+                        .into_iter()
+                        .map(|sponsor| view! { <p><a class="text-blue-700 hover:underline" href=member_url(&sponsor.bioguide_id)>{sponsor.full_name}</a></p> })
+                        .collect_view()}
+                </Field>
+                <Field label="Introduced">{introduced_date.unwrap_or_default()}</Field>
+                <Field label="Origin chamber">{origin_chamber.map(|chamber| chamber.to_string())}</Field>
+                <Field label="Policy area">{policy_area.map(|area| area.name)}</Field>
+                <Field label="Latest action">
+                    {latest_action.map(|action| view! { {action.action_date}" - "{action.text} })}
+                </Field>
+                {(!laws.is_empty()).then(|| view! {
+                    <Field label="Became law">
+                        {laws
+                            .into_iter()
+                            .map(|law| view! { <p>{law.law_type}" "{law.number}</p> })
+                            .collect_view()}
+                    </Field>
+                })}
+                {(!committee_reports.is_empty()).then(|| view! {
+                    <Field label="Committee reports">
+                        {committee_reports
+                            .into_iter()
+                            .map(|report| view! { <p>{report.citation}</p> })
+                            .collect_view()}
+                    </Field>
+                })}
+                {(!cbo_cost_estimates.is_empty()).then(|| view! {
+                    <Field label="CBO cost estimates">
+                        {cbo_cost_estimates
+                            .into_iter()
+                            .map(|estimate| view! {
+                                <p>
+                                    <a class="text-blue-700 hover:underline" href=estimate.url>{estimate.title}</a>
+                                    " "{estimate.pub_date}
+                                </p>
+                            })
+                            .collect_view()}
+                    </Field>
+                })}
+            </dl>
+        </Section>
+    }
+}
+
+#[component]
+fn TextVersionsSection(id: BillId) -> impl IntoView {
+    let base = id.url();
+    let versions = create_resource(move || id, get_bill_text_versions);
+
+    view! {
+        <Section title="Text">
+            <Transition fallback=move || view! { <p>"Loading text versions..."</p> }>
+                {move || versions.get().map(|versions| match versions {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(versions) => {
+                        let abbreviations = versions
+                            .iter()
+                            .filter_map(|version| version.abbreviation())
+                            .collect::<Vec<_>>();
+                        let stage = LegislativeStage::reached(&abbreviations)
+                            .map(|stage| view! { <StageProgress stage/> });
+                        view! {
+                            {stage}
+                            <TextVersionList versions base=base.clone()/>
+                        }.into_view()
+                    }
+                })}
+            </Transition>
+        </Section>
+    }
+}
+
+/// Actions newest first, laid out in House and Senate lanes with whole-bill actions spanning both.
+#[component]
+fn ActionsTimeline(id: BillId) -> impl IntoView {
+    let actions = create_resource(move || id, get_bill_actions);
+    let (action_type, set_action_type) = create_signal(String::new());
+
+    view! {
+        <Section title="Actions">
+            <Transition fallback=move || view! { <p>"Loading actions..."</p> }>
+                {move || actions.get().map(|actions| match actions {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(actions) if actions.is_empty() => view! { <p>"No actions yet."</p> }.into_view(),
+                    Ok(actions) => {
+                        let mut action_types: Vec<String> = actions
+                            .iter()
+                            .filter_map(|action| action.action_type.clone())
+                            .collect();
+                        action_types.sort();
+                        action_types.dedup();
+
+                        let filtered = move || {
+                            let selected = action_type.get();
+                            actions
+                                .iter()
+                                .filter(|action| {
+                                    selected.is_empty()
+                                        || action.action_type.as_deref() == Some(selected.as_str())
+                                })
+                                .cloned()
+                                .map(|action| view! { <TimelineEntry action/> })
+                                .collect_view()
+                        };
+
+                        view! {
+                            <label class="block mb-4 text-sm text-gray-700 dark:text-gray-400">
+                                "Action type "
+                                <select
+                                    class="ms-2 p-1 border border-gray-300 rounded"
+                                    on:change=move |ev| set_action_type.set(event_target_value(&ev))
+                                >
+                                    <option value="">"All"</option>
+                                    {action_types
+                                        .into_iter()
+                                        .map(|action_type| view! { <option value=action_type.clone()>{action_type.clone()}</option> })
+                                        .collect_view()}
+                                </select>
+                            </label>
+                            <div class="grid grid-cols-2 gap-2">
+                                <h3 class="font-medium text-gray-900 dark:text-white">"House"</h3>
+                                <h3 class="font-medium text-gray-900 dark:text-white">"Senate"</h3>
+                                {filtered}
+                            </div>
+                        }.into_view()
+                    }
+                })}
+            </Transition>
+        </Section>
+    }
+}
+
+#[component]
+fn TimelineEntry(action: BillAction) -> impl IntoView {
+    let lane = match action.chamber() {
+        Chamber::House => "col-start-1 border-blue-500",
+        Chamber::Senate => "col-start-2 border-red-500",
+        Chamber::Both => "col-span-2 border-gray-500",
+    };
+    let source = action.source_system.map(|system| system.name);
+
+    view! {
+        <div class=format!("{lane} p-2 border-l-4 bg-slate-50 dark:bg-gray-700")>
+            <p class="text-sm text-gray-500 dark:text-gray-400">
+                {action.action_date}" "{action.action_time}" | "{action.action_type}" "
+                {action.action_code.map(|code| format!("[{code}]"))}" "{source}
+            </p>
+            <p class="text-gray-900 dark:text-white">{action.text}</p>
+            {action
+                .recorded_votes
+                .into_iter()
+                .map(|vote| view! {
+                    <a class="me-2 text-sm text-blue-700 hover:underline" href=vote.url>
+                        {vote.chamber}" roll call "{vote.roll_number}" (session "{vote.session_number}")"
+                    </a>
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn CosponsorsSection(id: BillId) -> impl IntoView {
+    let cosponsors = create_resource(move || id, get_bill_cosponsors);
+    let bipartisanship = create_resource(move || id, get_bipartisanship);
+
+    view! {
+        <Section title="Cosponsors">
+            <Transition fallback=move || ()>
+                {move || bipartisanship.get().and_then(Result::ok).flatten().and_then(|bill| {
+                    let share = bill.share()?;
+                    Some(view! {
+                        <p class="mb-2 text-sm text-gray-500">
+                            {bill.across}" of "{bill.cosponsors}" cosponsors ("{format!("{:.0}%", share * 100.0)}") are from outside the sponsor's party."
+                        </p>
+                    })
+                })}
+            </Transition>
+            <Transition fallback=move || view! { <p>"Loading cosponsors..."</p> }>
+                {move || cosponsors.get().map(|cosponsors| match cosponsors {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(cosponsors) if cosponsors.is_empty() => view! { <p>"No cosponsors."</p> }.into_view(),
+                    Ok(cosponsors) => view! {
+                        <ul>
+                            {cosponsors
+                                .into_iter()
+                                .map(|cosponsor| view! {
+                                    <li>
+                                        <a class="text-blue-700 hover:underline" href=member_url(&cosponsor.bioguide_id)>{cosponsor.full_name}</a>
+                                        " - "{cosponsor.sponsorship_date}
+                                        {cosponsor.is_original_cosponsor.then_some(" (original)")}
+                                        {cosponsor.sponsorship_withdrawn_date.map(|date| format!(" (withdrawn {date})"))}
+                                    </li>
+                                })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                })}
+            </Transition>
+        </Section>
+    }
+}
+
+#[component]
+fn CommitteesSection(id: BillId) -> impl IntoView {
+    let committees = create_resource(move || id, get_bill_committees);
+
+    view! {
+        <Section title="Committees">
+            <Transition fallback=move || view! { <p>"Loading committees..."</p> }>
+                {move || committees.get().map(|committees| match committees {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(committees) if committees.is_empty() => view! { <p>"Not referred to any committee."</p> }.into_view(),
+                    Ok(committees) => view! {
+                        <ul>
+                            {committees
+                                .into_iter()
+                                .map(|committee| {
+                                    let activities = committee
+                                        .activities
+                                        .into_iter()
+                                        .map(|activity| activity.name)
+                                        .collect::<Vec<_>>()
+                                        .join(", ");
+                                    view! {
+                                        <li>
+                                            <span class="font-medium">{committee.name}</span>
+                                            " ("{committee.chamber}") "{activities}
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                })}
+            </Transition>
+        </Section>
+    }
+}
+
+#[component]
+fn SubjectsSection(id: BillId) -> impl IntoView {
+    let subjects = create_resource(move || id, get_bill_subjects);
+
+    view! {
+        <Section title="Subjects">
+            <Transition fallback=move || view! { <p>"Loading subjects..."</p> }>
+                {move || subjects.get().map(|subjects| match subjects {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(subjects) if subjects.legislative_subjects.is_empty() => view! { <p>"No subjects assigned yet."</p> }.into_view(),
+                    Ok(subjects) => view! {
+                        <ul class="flex flex-wrap gap-2">
+                            {subjects
+                                .legislative_subjects
+                                .into_iter()
+                                .map(|subject| view! {
+                                    <li class="px-2 py-1 text-sm bg-slate-100 rounded dark:bg-gray-700">{subject.name}</li>
+                                })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                })}
+            </Transition>
+        </Section>
+    }
+}
+
+#[component]
+fn RelatedBillsSection(id: BillId) -> impl IntoView {
+    let related = create_resource(move || id, get_related_bills);
+
+    view! {
+        <Section title="Related bills">
+            <Transition fallback=move || view! { <p>"Loading related bills..."</p> }>
+                {move || related.get().map(|related| match related {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(related) if related.is_empty() => view! { <p>"No related bills."</p> }.into_view(),
+                    Ok(related) => view! {
+                        <ul>
+                            {related
+                                .into_iter()
+                                .map(|bill| {
+                                    let relationship = bill
+                                        .relationship_details
+                                        .into_iter()
+                                        .map(|detail| detail.relationship_type)
+                                        .collect::<Vec<_>>()
+                                        .join(", ");
+                                    view! {
+                                        <li>
+                                            <a class="text-blue-700 hover:underline" href=BillId::new(bill.congress, bill.bill_type, bill.number).url()>
+                                                {bill.bill_type}" "{bill.number}
+                                            </a>
+                                            " "{bill.title}" ("{relationship}")"
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                })}
+            </Transition>
+        </Section>
+    }
+}
+
+/// Redirects the old `/bills/:bill_type/:bill_number` links to the bill in the current Congress.
+#[component]
+pub fn CurrentCongressBill() -> impl IntoView {
+    let params = use_params::<LegacyBillParams>();
+
+    move || {
+        let id = params.get().ok().and_then(|params| {
+            format!("{}{}", params.bill_type, params.bill_number)
+                .parse::<BillId>()
+                .ok()
+        });
+        match id {
+            Some(id) => view! { <Redirect path=id.url()/> }.into_view(),
+            None => view! { <NotFound/> }.into_view(),
+        }
+    }
+}
+
+/// Parsed from the path, so a bill type or number that doesn't exist never reaches a server fn.
 #[derive(Params, PartialEq, Eq, Clone)]
 pub struct BillParams {
+    congress: u32,
+    bill_type: BillType,
+    bill_number: u32,
+}
+
+impl BillParams {
+    pub fn id(&self) -> BillId {
+        BillId::new(self.congress, self.bill_type, self.bill_number)
+    }
+}
+
+#[derive(Params, PartialEq, Eq, Clone)]
+pub struct LegacyBillParams {
     bill_type: String,
     bill_number: String,
 }
 
 #[server]
-async fn get_bill(bill_type: String, bill_number: String) -> Result<Bill, ServerFnError> {
-    let client = reqwest::Client::new();
-    let token = dotenvy_macro::dotenv!("CONGRESS_GOV_API_TOKEN");
+async fn get_bill(id: BillId) -> Result<BillDetail, ServerFnError> {
+    use crate::congress::client;
+    use crate::db::db;
+    use crate::domain::bills::BillListItem;
+    use crate::ingest::store_bill;
+    use leptos::logging::warn;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Stored {
+        #[serde(rename = "updateDate")]
+        update_date: Option<String>,
+        detail: Option<BillDetail>,
+    }
 
-    let res = client
-        .get(format!(
-            "https://api.congress.gov/v3/bill/118/{}/{}?format=json&api_key={}",
-            bill_type.to_lowercase(),
-            bill_number,
-            token
-        ))
-        .send()
-        .await?
-        .text()
-        .await?;
+    let db = db()?;
 
-    dbg!(&res);
+    // The sync updates `updateDate` when the bill changes, so a detail with the same date is current.
+    let stored: Option<Stored> = db.select((BillId::TABLE, id.key())).await?;
+    let stored = match stored {
+        Some(Stored {
+            update_date,
+            detail: Some(detail),
+        }) if detail.update_date == update_date => return Ok(detail),
+        Some(stored) => stored.detail,
+        None => None,
+    };
 
-    let res: Response = serde_json::from_str(res.as_str())?;
-    let bill = res.bill;
+    let fetched = match client() {
+        Ok(client) => client.bill(&id).await.map_err(ServerFnError::from),
+        Err(error) => Err(error),
+    };
+    match (fetched, stored) {
+        (Ok(bill), _) => {
+            store_bill(&db, &id, &BillListItem::from(&bill), Some(&bill)).await?;
+            Ok(bill)
+        }
+        (Err(error), Some(stored)) => {
+            warn!("serving stored {id}, Congress.gov failed: {error}");
+            Ok(stored)
+        }
+        (Err(error), None) => Err(error),
+    }
+}
 
-    dbg!("{:?}", &bill);
+#[server]
+async fn get_bill_actions(id: BillId) -> Result<Vec<BillAction>, ServerFnError> {
+    use crate::congress::client;
+    let client = client()?;
 
-    Ok(bill)
+    Ok(client.bill_actions(&id).await?)
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Bill {
-    title: String,
+#[server]
+async fn get_bill_cosponsors(id: BillId) -> Result<Vec<Cosponsor>, ServerFnError> {
+    use crate::congress::client;
+    let client = client()?;
+
+    Ok(client.bill_cosponsors(&id).await?)
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Response {
-    bill: Bill,
+/// `None` until the background sync stored the bill's sponsorship edges.
+#[server]
+async fn get_bipartisanship(id: BillId) -> Result<Option<Bipartisanship>, ServerFnError> {
+    use crate::db::db;
+    use crate::graph::bipartisanship;
+
+    let db = db()?;
+    Ok(bipartisanship(&db, &id).await?)
+}
+
+#[server]
+async fn get_bill_committees(id: BillId) -> Result<Vec<BillCommittee>, ServerFnError> {
+    use crate::congress::client;
+    let client = client()?;
+
+    Ok(client.bill_committees(&id).await?)
+}
+
+#[server]
+async fn get_bill_subjects(id: BillId) -> Result<BillSubjects, ServerFnError> {
+    use crate::congress::client;
+    let client = client()?;
+
+    Ok(client.bill_subjects(&id).await?)
+}
+
+#[server]
+async fn get_related_bills(id: BillId) -> Result<Vec<RelatedBill>, ServerFnError> {
+    use crate::congress::client;
+    let client = client()?;
+
+    Ok(client.bill_related_bills(&id).await?)
 }
@@ -0,0 +1,245 @@
+use leptos::*;
+use leptos_router::{use_navigate, use_params, use_query_map, Params, A};
+
+use crate::app::components::api_key::ServerErrorMessage;
+use crate::app::routes::bills::text::get_bill_text_versions;
+use crate::domain::bills::{BillId, BillType};
+use crate::domain::text_diff::{BillTextDiff, ChangeKind, DiffSpan, SectionDiff, SectionStatus};
+use crate::error_template::NotFound;
+
+/// Redline of two text versions at `/bills/:congress/:bill_type/:bill_number/compare/:from/:to`.
+///
+/// Renders inline by default, `?view=side` shows the versions side by side.
+#[component]
+pub fn BillTextCompare() -> impl IntoView {
+    let params = use_params::<CompareParams>();
+    // Only remounts when the path starts or stops parsing, so switching versions keeps the page.
+    let valid = create_memo(move |_| params.with(Result::is_ok));
+
+    move || {
+        if valid.get() {
+            // Keeps the last parsed params until the page is swapped for the 404.
+            let params = create_memo(move |last: Option<&CompareParams>| {
+                params
+                    .get()
+                    .ok()
+                    .or_else(|| last.cloned())
+                    .expect("the page is only mounted once the params parse")
+            });
+            view! { <BillTextComparePage params=params.into()/> }.into_view()
+        } else {
+            view! { <NotFound/> }.into_view()
+        }
+    }
+}
+
+#[component]
+fn BillTextComparePage(params: Signal<CompareParams>) -> impl IntoView {
+    let query = use_query_map();
+    let side_by_side =
+        move || query.with(|query| query.get("view").map(String::as_str) == Some("side"));
+
+    let versions = create_resource(move || params.get().id(), get_bill_text_versions);
+    let diff = create_resource(
+        move || params.get(),
+        |p| async move { get_bill_text_diff(p.id(), p.from, p.to).await },
+    );
+    let base = move || params.get().id().url();
+
+    let navigate = use_navigate();
+    let select_versions = move |from: Option<String>, to: Option<String>| {
+        let p = params.get_untracked();
+        let view = if side_by_side() { "?view=side" } else { "" };
+        navigate(
+            &format!(
+                "{}/compare/{}/{}{view}",
+                base(),
+                from.unwrap_or(p.from),
+                to.unwrap_or(p.to)
+            ),
+            Default::default(),
+        );
+    };
+
+    view! {
+        <div class="bg-slate-100">
+            <a class="m-4 text-blue-700 hover:underline" href=base>"Back to bill"</a>
+            <Transition fallback=move || view! { <p>"Loading versions..."</p> }>
+                {move || versions.get().map(|versions| match versions {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(versions) => {
+                        let codes: Vec<String> = versions
+                            .iter()
+                            .filter_map(|version| version.abbreviation())
+                            .map(|abbreviation| abbreviation.code().to_lowercase())
+                            .collect();
+                        let p = params.get();
+                        let select_from = select_versions.clone();
+                        let select_to = select_versions.clone();
+                        view! {
+                            <div class="flex gap-4 m-4 items-center">
+                                <VersionSelect codes=codes.clone() selected=p.from on_select=move |code| select_from(Some(code), None)/>
+                                "→"
+                                <VersionSelect codes selected=p.to on_select=move |code| select_to(None, Some(code))/>
+                                <A class="text-blue-700 hover:underline" href=move || format!("?view={}", if side_by_side() { "inline" } else { "side" })>
+                                    {move || if side_by_side() { "Inline view" } else { "Side by side" }}
+                                </A>
+                            </div>
+                        }.into_view()
+                    }
+                })}
+            </Transition>
+            <Transition fallback=move || view! { <p>"Comparing versions..."</p> }>
+                {move || diff.get().map(|diff| match diff {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(diff) => view! { <DiffView diff side_by_side=side_by_side()/> }.into_view(),
+                })}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn VersionSelect(
+    codes: Vec<String>,
+    selected: String,
+    #[prop(into)] on_select: Callback<String>,
+) -> impl IntoView {
+    view! {
+        <select
+            class="p-1 border border-gray-300 rounded"
+            on:change=move |ev| on_select.call(event_target_value(&ev))
+        >
+            {codes
+                .into_iter()
+                .map(|code| {
+                    let is_selected = code == selected;
+                    view! { <option value=code.clone() selected=is_selected>{code.to_uppercase()}</option> }
+                })
+                .collect_view()}
+        </select>
+    }
+}
+
+#[component]
+fn DiffView(diff: BillTextDiff, side_by_side: bool) -> impl IntoView {
+    let summary = diff.summary;
+    let changed: Vec<_> = diff
+        .sections
+        .iter()
+        .filter(|section| section.status != SectionStatus::Unchanged)
+        .map(|section| {
+            let href = format!("#{}", section.anchor());
+            let label = format!("{} ({:?})", section.heading, section.status);
+            view! { <li><a class="text-blue-700 hover:underline" href=href>{label}</a></li> }
+        })
+        .collect();
+
+    view! {
+        <section class="m-4 p-6 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
+            <h1 class="mb-2 text-xl font-bold text-gray-900 dark:text-white">
+                {diff.from.to_string()}" → "{diff.to.to_string()}
+            </h1>
+            <p class="mb-2 text-gray-700 dark:text-gray-400">
+                {summary.added}" sections added, "{summary.removed}" removed, "
+                {summary.modified}" modified, "{summary.unchanged}" unchanged"
+            </p>
+            <ul class="text-sm">{changed}</ul>
+        </section>
+        {diff
+            .sections
+            .into_iter()
+            .map(|section| view! { <SectionView section side_by_side/> })
+            .collect_view()}
+    }
+}
+
+#[component]
+fn SectionView(section: SectionDiff, side_by_side: bool) -> impl IntoView {
+    let badge = match section.status {
+        SectionStatus::Added => "bg-green-100 text-green-800",
+        SectionStatus::Removed => "bg-red-100 text-red-800",
+        SectionStatus::Modified => "bg-yellow-100 text-yellow-800",
+        SectionStatus::Unchanged => "bg-gray-100 text-gray-800",
+    };
+    let body = if side_by_side {
+        let old = section
+            .spans
+            .iter()
+            .filter(|span| span.kind != ChangeKind::Insert);
+        let new = section
+            .spans
+            .iter()
+            .filter(|span| span.kind != ChangeKind::Delete);
+        view! {
+            <div class="grid grid-cols-2 gap-4">
+                <pre class="whitespace-pre-wrap">{old.cloned().map(|span| view! { <Span span/> }).collect_view()}</pre>
+                <pre class="whitespace-pre-wrap">{new.cloned().map(|span| view! { <Span span/> }).collect_view()}</pre>
+            </div>
+        }
+        .into_view()
+    } else {
+        view! {
+            <pre class="whitespace-pre-wrap">
+                {section.spans.iter().cloned().map(|span| view! { <Span span/> }).collect_view()}
+            </pre>
+        }
+        .into_view()
+    };
+
+    view! {
+        <section id=section.anchor() class="m-4 p-6 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
+            <h2 class="mb-2 font-bold text-gray-900 dark:text-white">
+                {section.heading.clone()}" "
+                <span class=format!("px-2 py-1 text-xs rounded {badge}")>{format!("{:?}", section.status)}</span>
+            </h2>
+            {body}
+        </section>
+    }
+}
+
+#[component]
+fn Span(span: DiffSpan) -> impl IntoView {
+    match span.kind {
+        ChangeKind::Equal => view! { <span>{span.text}</span> }.into_view(),
+        ChangeKind::Insert => {
+            view! { <ins class="bg-green-100 text-green-900">{span.text}</ins> }.into_view()
+        }
+        ChangeKind::Delete => {
+            view! { <del class="bg-red-100 text-red-900">{span.text}</del> }.into_view()
+        }
+    }
+}
+
+#[derive(Params, PartialEq, Eq, Clone)]
+pub struct CompareParams {
+    congress: u32,
+    bill_type: BillType,
+    bill_number: u32,
+    from: String,
+    to: String,
+}
+
+impl CompareParams {
+    pub fn id(&self) -> BillId {
+        BillId::new(self.congress, self.bill_type, self.bill_number)
+    }
+}
+
+#[server]
+async fn get_bill_text_diff(
+    id: BillId,
+    from: String,
+    to: String,
+) -> Result<BillTextDiff, ServerFnError> {
+    use crate::app::routes::bills::text::{find_version, plain_text};
+    use crate::congress::client;
+    let client = client()?;
+
+    let (from, from_version) = find_version(&client, &id, &from).await?;
+    let (to, to_version) = find_version(&client, &id, &to).await?;
+    let from_text = plain_text(&client, &from_version).await?;
+    let to_text = plain_text(&client, &to_version).await?;
+
+    Ok(BillTextDiff::new(from, to, &from_text, &to_text))
+}
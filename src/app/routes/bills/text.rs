@@ -0,0 +1,308 @@
+use leptos::*;
+use leptos_router::{use_params, Params, A};
+use serde::{Deserialize, Serialize};
+
+use crate::app::components::api_key::ServerErrorMessage;
+use crate::domain::bills::{BillId, BillTextVersionAbbreviation, BillType, TextVersion};
+use crate::error_template::NotFound;
+
+/// Text of one version of a bill at `/bills/:congress/:bill_type/:bill_number/text/:version`.
+#[component]
+pub fn BillText() -> impl IntoView {
+    let params = use_params::<BillTextParams>();
+    // Only remounts when the path starts or stops parsing, so switching versions keeps the page.
+    let valid = create_memo(move |_| params.with(Result::is_ok));
+
+    move || {
+        if valid.get() {
+            // Keeps the last parsed params until the page is swapped for the 404.
+            let params = create_memo(move |last: Option<&BillTextParams>| {
+                params
+                    .get()
+                    .ok()
+                    .or_else(|| last.cloned())
+                    .expect("the page is only mounted once the params parse")
+            });
+            view! { <BillTextPage params=params.into()/> }.into_view()
+        } else {
+            view! { <NotFound/> }.into_view()
+        }
+    }
+}
+
+#[component]
+fn BillTextPage(params: Signal<BillTextParams>) -> impl IntoView {
+    // Keyed on the bill only, so switching versions doesn't reload the list.
+    let versions = create_resource(move || params.get().id(), get_bill_text_versions);
+    let document = create_resource(
+        move || params.get(),
+        |p| async move { get_bill_text(p.id(), p.version).await },
+    );
+    let base = move || params.get().id().url();
+    let current = move || BillTextVersionAbbreviation::from_code(&params.get().version);
+
+    view! {
+        <div class="bg-slate-100">
+            <a class="m-4 text-blue-700 hover:underline" href=base>"Back to bill"</a>
+            <Transition fallback=move || view! { <p>"Loading versions..."</p> }>
+                {move || versions.get().map(|versions| match versions {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(versions) => view! {
+                        <TextVersionList versions base=base() current=current()/>
+                    }.into_view(),
+                })}
+            </Transition>
+            <Transition fallback=move || view! { <p>"Loading text..."</p> }>
+                {move || versions.get().and_then(Result::ok).and_then(|versions| {
+                    // Offer a redline against the version before this one.
+                    let current = current()?;
+                    let position = versions
+                        .iter()
+                        .position(|version| version.abbreviation().as_ref() == Some(&current))?;
+                    let previous = versions[..position]
+                        .iter()
+                        .rev()
+                        .find_map(TextVersion::abbreviation)?;
+                    let href = format!(
+                        "{}/compare/{}/{}",
+                        base(),
+                        previous.code().to_lowercase(),
+                        current.code().to_lowercase()
+                    );
+                    Some(view! {
+                        <A class="m-4 text-blue-700 hover:underline" href>
+                            "Compare with "{previous.code().to_string()}
+                        </A>
+                    })
+                })}
+                {move || document.get().map(|document| match document {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(document) => view! { <TextDocument document/> }.into_view(),
+                })}
+            </Transition>
+        </div>
+    }
+}
+
+/// Links to every text version of a bill, in the order of [`get_bill_text_versions`].
+#[component]
+pub fn TextVersionList(
+    versions: Vec<TextVersion>,
+    /// Path of the bill page, e.g. `/bills/118/hr/7261`.
+    base: String,
+    #[prop(optional)] current: Option<BillTextVersionAbbreviation>,
+) -> impl IntoView {
+    if versions.is_empty() {
+        return view! { <p class="m-4">"No text has been published yet."</p> }.into_view();
+    }
+
+    view! {
+        <ul class="flex flex-wrap gap-2 m-4">
+            {versions
+                .into_iter()
+                .filter_map(|version| {
+                    let abbreviation = version.abbreviation()?;
+                    let class = if current.as_ref() == Some(&abbreviation) {
+                        "block px-3 py-2 text-sm text-white bg-blue-700 rounded-lg"
+                    } else {
+                        "block px-3 py-2 text-sm text-gray-900 bg-white border border-gray-200 rounded-lg hover:bg-gray-100"
+                    };
+                    let href = format!("{base}/text/{}", abbreviation.code().to_lowercase());
+                    let date = version.date.as_deref().map(|date| date.chars().take(10).collect::<String>());
+                    Some(view! {
+                        <li>
+                            <A href class>
+                                <span class="font-medium">{abbreviation.code().to_string()}</span>" "{abbreviation.to_string()}
+                                <span class="block text-xs">{date}</span>
+                            </A>
+                        </li>
+                    })
+                })
+                .collect_view()}
+        </ul>
+    }
+    .into_view()
+}
+
+#[component]
+fn TextDocument(document: BillTextDocument) -> impl IntoView {
+    let content = match document.content {
+        TextContent::Html(html) => {
+            view! { <div class="overflow-x-auto" inner_html=html></div> }.into_view()
+        }
+        TextContent::Plain(text) => {
+            view! { <pre class="overflow-x-auto whitespace-pre-wrap">{text}</pre> }.into_view()
+        }
+    };
+
+    view! {
+        <article class="m-4 p-6 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
+            <h1 class="mb-2 text-xl font-bold text-gray-900 dark:text-white">
+                {document.version.to_string()}" ("{document.version.code().to_string()}")"
+            </h1>
+            <p class="mb-4 text-sm text-gray-500">
+                {document.date}" "
+                {document.pdf_url.map(|url| view! { <a class="text-blue-700 hover:underline" href=url>"PDF"</a> })}
+            </p>
+            {content}
+        </article>
+    }
+}
+
+#[derive(Params, PartialEq, Eq, Clone)]
+pub struct BillTextParams {
+    congress: u32,
+    bill_type: BillType,
+    bill_number: u32,
+    version: String,
+}
+
+impl BillTextParams {
+    pub fn id(&self) -> BillId {
+        BillId::new(self.congress, self.bill_type, self.bill_number)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum TextContent {
+    /// The body of the GPO "Formatted Text" HTML file.
+    Html(String),
+    /// Raw XML, shown as is when no HTML rendition exists.
+    Plain(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BillTextDocument {
+    pub version: BillTextVersionAbbreviation,
+    pub date: Option<String>,
+    pub pdf_url: Option<String>,
+    pub content: TextContent,
+}
+
+/// Text versions of a bill in the order it moved through Congress, see [`TextVersion::sort`].
+#[server]
+pub async fn get_bill_text_versions(id: BillId) -> Result<Vec<TextVersion>, ServerFnError> {
+    use crate::congress::client;
+    let client = client()?;
+
+    let mut versions = client.bill_text(&id).await?;
+    TextVersion::sort(&mut versions);
+
+    Ok(versions)
+}
+
+#[server]
+pub async fn get_bill_text(id: BillId, version: String) -> Result<BillTextDocument, ServerFnError> {
+    use crate::congress::client;
+    let client = client()?;
+
+    let (abbreviation, text_version) = find_version(&client, &id, &version).await?;
+
+    let content = if let Some(url) = text_version.html_url() {
+        TextContent::Html(document_body(&client.text_document(url).await?))
+    } else if let Some(url) = text_version.xml_url() {
+        TextContent::Plain(client.text_document(url).await?)
+    } else {
+        return Err(ServerFnError::ServerError(
+            "This version is only published as PDF.".into(),
+        ));
+    };
+
+    Ok(BillTextDocument {
+        version: abbreviation,
+        date: text_version.date.clone(),
+        pdf_url: text_version.pdf_url().map(str::to_string),
+        content,
+    })
+}
+
+/// Looks up one text version of a bill by its GPO code.
+#[cfg(feature = "ssr")]
+pub(crate) async fn find_version(
+    client: &crate::congress::CongressClient,
+    id: &BillId,
+    code: &str,
+) -> Result<(BillTextVersionAbbreviation, TextVersion), ServerFnError> {
+    let abbreviation = BillTextVersionAbbreviation::from_code(code)
+        .ok_or_else(|| ServerFnError::ServerError(format!("Unknown text version `{code}`.")))?;
+    let text_version = client
+        .bill_text(id)
+        .await?
+        .into_iter()
+        .find(|text_version| text_version.abbreviation().as_ref() == Some(&abbreviation))
+        .ok_or_else(|| {
+            ServerFnError::ServerError(format!("No {abbreviation} text for this bill."))
+        })?;
+
+    Ok((abbreviation, text_version))
+}
+
+/// The text of a version without markup, as used for comparing versions.
+#[cfg(feature = "ssr")]
+pub(crate) async fn plain_text(
+    client: &crate::congress::CongressClient,
+    text_version: &TextVersion,
+) -> Result<String, ServerFnError> {
+    use once_cell::sync::Lazy;
+    use regex::Regex;
+
+    static TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>").unwrap());
+
+    let url = text_version
+        .html_url()
+        .or(text_version.xml_url())
+        .ok_or_else(|| {
+            ServerFnError::ServerError("This version is only published as PDF.".into())
+        })?;
+    let document = client.text_document(url).await?;
+    let text = TAG.replace_all(&document_body(&document), "").into_owned();
+
+    Ok(htmlize::unescape(text).into_owned())
+}
+
+/// Keeps what's inside `<body>` of a GPO text file, sanitized with ammonia's allow-list since it
+/// is rendered with `inner_html`: scripts, styles, frames, event handlers and `javascript:` URLs
+/// are dropped.
+#[cfg(feature = "ssr")]
+fn document_body(html: &str) -> String {
+    use once_cell::sync::Lazy;
+    use regex::Regex;
+
+    static BODY: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?is)<body[^>]*>(?<body>.*)</body>").unwrap());
+
+    let body = BODY
+        .captures(html)
+        .map_or(html, |caps| caps.name("body").unwrap().as_str());
+    ammonia::clean(body)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_body_is_sanitized() {
+        let html = r#"<html><head><title>H.R. 7261</title></head><body>
+            <pre>SEC. 2. <a href="https://www.congress.gov">FINDINGS</a>.</pre>
+            <img src="x" onerror="alert(1)"><a href="javascript:alert(1)">link</a>
+            <script>alert(1)</script><style>pre { display: none }</style>
+            <iframe src="https://example.com"></iframe><svg onload="alert(1)"></svg>
+        </body></html>"#;
+        let body = document_body(html);
+
+        assert!(body.contains("<pre>SEC. 2. <a href=\"https://www.congress.gov\""));
+        assert!(!body.contains("H.R. 7261"));
+        for unsafe_part in [
+            "onerror",
+            "javascript:",
+            "<script",
+            "<style",
+            "<iframe",
+            "<svg",
+            "onload",
+        ] {
+            assert!(!body.contains(unsafe_part), "{unsafe_part} in {body}");
+        }
+    }
+}
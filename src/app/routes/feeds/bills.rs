@@ -1,9 +1,9 @@
 use leptos::*;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
 
-use crate::domain::bills::BillType;
+use crate::app::components::stage_progress::StageProgress;
+use crate::domain::bills::{ordinal, BillTextVersionAbbreviation, LegislativeStage};
+use crate::domain::feeds::bills::{BillItem, FeedEntry};
 
 #[component]
 pub fn BillsFeed() -> impl IntoView {
@@ -15,140 +15,209 @@ pub fn BillsFeed() -> impl IntoView {
             <Transition
                 fallback=move || view! { <p>"Loading..."</p> }
             >
-                <ul>
                 {move || match items.get() {
                     None => view! { <h1>"No items found in Feed."</h1> }.into_view(),
                     Some(Err(_)) => view! { <p>"Error loading feed."</p> }.into_view(),
-                    Some(Ok(items)) => {
-                        {items.into_iter()
-                            .map(|bill| view! { <BillsFeedCard bill/> })
-                            .collect_view()}                }
-                            }}
-                </ul>
+                    Some(Ok(items)) => view! {
+                        {(items.rejected > 0).then(|| view! {
+                            <p class="m-4 text-sm text-gray-500">
+                                {items.rejected}" feed items could not be read and were skipped."
+                            </p>
+                        })}
+                        {(items.new > 0).then(|| view! {
+                            <p class="m-4 font-medium text-blue-700">
+                                {items.new}" new since your last visit"
+                            </p>
+                        })}
+                        <ul>
+                            {items.entries
+                                .into_iter()
+                                .map(|entry| view! { <BillsFeedCard bill=entry.item is_new=entry.is_new/> })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                }}
             </Transition>
         </div>
     }
 }
 
+/// Badge colors by stage, with versions printed for a failed floor vote in red.
+fn version_badge_class(version: &BillTextVersionAbbreviation) -> &'static str {
+    use BillTextVersionAbbreviation::*;
+    use LegislativeStage::*;
+    if matches!(version, FPH | FPS | IPH | IPS | LTH | LTS) {
+        return "bg-red-100 text-red-800";
+    }
+    match version.stage() {
+        None | Some(Introduced) => "bg-gray-100 text-gray-800",
+        Some(Referred | Reported) => "bg-yellow-100 text-yellow-800",
+        Some(Engrossed | PassedOtherChamber) => "bg-blue-100 text-blue-800",
+        Some(Enrolled) => "bg-green-100 text-green-800",
+        Some(Law) => "bg-purple-100 text-purple-800",
+    }
+}
+
 #[component]
-fn BillsFeedCard(bill: BillItem) -> impl IntoView {
+pub(crate) fn BillsFeedCard(bill: BillItem, is_new: bool) -> impl IntoView {
+    let href = bill.id().url();
+    let stage = bill
+        .bill_version
+        .as_ref()
+        .and_then(|version| version.stage())
+        .map(|stage| view! { <StageProgress stage/> });
+    let version = bill.bill_version.map(|version| {
+        let class = format!(
+            "me-2 px-2.5 py-0.5 text-xs font-medium rounded {}",
+            version_badge_class(&version)
+        );
+        view! {
+            <p class="mb-2">
+                <span class=class>{version.code().to_string()}</span>
+                <span class="text-sm text-gray-500 dark:text-gray-400">{version.to_string()}</span>
+            </p>
+        }
+    });
     view! {
-            <ErrorBoundary
-                // the fallback receives a signal containing current errors
-                fallback=|_| view! {
-                    <li class="bg-slate-500 text-blue-700 p-4 m-4">
-                        <p>"Error loading item."</p>
-                    </li>
-                }
-            >
             <li class="m-4 p-4 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
-                <h5 class="mb-2 text-2xl font-bold tracking-tight text-gray-900 dark:text-white">{bill.bill_type.clone()}" "{bill.bill_number.clone()}" ("{bill.bill_version}")"</h5>
+                <h5 class="mb-2 text-2xl font-bold tracking-tight text-gray-900 dark:text-white">
+                    {bill.bill_type}" "{bill.bill_number}" ("{ordinal(bill.congress)}")"
+                    {is_new.then(|| view! { <span class="ms-2 px-2.5 py-0.5 text-xs font-medium rounded bg-blue-600 text-white">"New"</span> })}
+                </h5>
+                {version}
+                {stage}
                 <p class="mb-3 font-normal text-gray-700 dark:text-gray-400">{bill.title}</p>
-                <a href={format!("/bills/{}/{}", bill.bill_type.unwrap(), bill.bill_number.unwrap())} class="inline-flex items-center px-3 py-2 text-sm font-medium text-center text-white bg-blue-700 rounded-lg hover:bg-blue-800 focus:ring-4 focus:outline-none focus:ring-blue-300 dark:bg-blue-600 dark:hover:bg-blue-700 dark:focus:ring-blue-800">
+                <a href=href class="inline-flex items-center px-3 py-2 text-sm font-medium text-center text-white bg-blue-700 rounded-lg hover:bg-blue-800 focus:ring-4 focus:outline-none focus:ring-blue-300 dark:bg-blue-600 dark:hover:bg-blue-700 dark:focus:ring-blue-800">
                 "Get Details"
                 <svg class="rtl:rotate-180 w-3.5 h-3.5 ms-2" aria-hidden="true" xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 14 10">
                     <path stroke="currentColor" stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M1 5h12m0 0L9 1m4 4L9 9"/>
                 </svg>
                 </a>
             </li>
-         </ErrorBoundary>
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub enum FeedErrors {
-    ParseError,
-    MatchError,
-}
+/// Feed items shown on the page, newest first.
+#[cfg(feature = "ssr")]
+const FEED_LIMIT: usize = 200;
 
-impl Display for FeedErrors {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            FeedErrors::ParseError => write!(f, "Error parsing Bill Number."),
-            FeedErrors::MatchError => write!(f, "Error matching Bill Type."),
-        }
+#[cfg(feature = "ssr")]
+const LAST_VISIT_COOKIE: &str = "feeds_last_visit";
+
+#[server]
+async fn get_bills_feed() -> Result<BillItems, ServerFnError> {
+    use crate::config::PollerSettings;
+    use crate::db::db;
+    use crate::domain::feeds::Collection;
+    use crate::poller;
+    use chrono::Utc;
+    use surrealdb::sql::Datetime;
+
+    let db = db()?;
+    if let Some(settings) = use_context::<PollerSettings>() {
+        poller::poll_on_request(&db, &settings, Collection::Bills).await?;
     }
+    let rejected: Option<usize> = db
+        .query(
+            "SELECT VALUE rejected FROM feed_fetch WHERE source = $source AND status = 200 \
+             ORDER BY fetched_at DESC LIMIT 1",
+        )
+        .bind(("source", Collection::Bills.slug()))
+        .await?
+        .take(0)?;
+
+    // Nothing is new on a first visit.
+    let since = last_visit().unwrap_or_else(Utc::now);
+    let entries: Vec<FeedEntry> = db
+        .query(
+            "SELECT item, guid, link, first_seen, first_seen > $since AS is_new \
+             FROM feed_item ORDER BY first_seen DESC LIMIT $limit",
+        )
+        .bind(("since", Datetime::from(since)))
+        .bind(("limit", FEED_LIMIT))
+        .await?
+        .take(0)?;
+    remember_visit();
+
+    Ok(BillItems {
+        new: entries.iter().filter(|entry| entry.is_new).count(),
+        entries,
+        rejected: rejected.unwrap_or_default(),
+    })
 }
 
-impl std::error::Error for FeedErrors {}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BillItems {
+    entries: Vec<FeedEntry>,
+    /// Entries first seen since the previous visit.
+    new: usize,
+    /// Items whose title could not be read in the poller's last full fetch of the feed.
+    rejected: usize,
+}
 
-#[server]
-async fn get_bills_feed() -> Result<Vec<BillItem>, ServerFnError> {
-    use rss::Channel;
+/// When the reader last opened the feed, from the `feeds_last_visit` cookie.
+#[cfg(feature = "ssr")]
+fn last_visit() -> Option<chrono::DateTime<chrono::Utc>> {
+    let parts = use_context::<http::request::Parts>()?;
+    last_visit_in(&parts.headers)
+}
 
-    let content = reqwest::get("https://www.govinfo.gov/rss/bills.xml")
-        .await?
-        .bytes()
-        .await?;
-    let channel = Channel::read_from(&content[..])?;
-    let items = channel.into_items();
-    let bill_items: Vec<BillItem> = items
-        .into_iter()
-        .map(|item| {
-            let title = item.title.unwrap_or("No title found for Bill.".to_string());
-            let escaped_title = htmlize::unescape(title);
-            let bill_item = parse_bill_item(escaped_title.as_ref());
-            bill_item
-        })
-        .collect();
-
-    Ok(bill_items)
+#[cfg(feature = "ssr")]
+fn last_visit_in(headers: &http::HeaderMap) -> Option<chrono::DateTime<chrono::Utc>> {
+    headers
+        .get_all(http::header::COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|header| header.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == LAST_VISIT_COOKIE)
+        .and_then(|(_, value)| value.parse().ok())
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
 }
 
-fn parse_bill_item(input: &str) -> BillItem {
-    println!("{:?}", input);
-    let re = Regex::new(
-        r"(?<bill_type>[a-zA-Z. ]+)( |\u{a0})(?<bill_number>\d+)( |\u{a0})\((?<bill_version>\w+)\) - (?<title>.*$)",
-    )
-    .unwrap();
-    let caps = re.captures(input).unwrap();
-    let bill_type = match caps["bill_type"]
-        .replace(['.', ' '], "")
-        .to_uppercase()
-        .as_str()
-    {
-        "HR" => Ok(BillType::HR),
-        "S" => Ok(BillType::S),
-        "HRES" => Ok(BillType::HRES),
-        "SRES" => Ok(BillType::SRES),
-        "HJRES" => Ok(BillType::HJRES),
-        "SJRES" => Ok(BillType::SJRES),
-        "HCONRES" => Ok(BillType::HCONRES),
-        "SCONRES" => Ok(BillType::SCONRES),
-        _ => Err(FeedErrors::MatchError),
+#[cfg(feature = "ssr")]
+fn remember_visit() {
+    use http::header::{HeaderValue, SET_COOKIE};
+    use leptos_axum::ResponseOptions;
+
+    let Some(response) = use_context::<ResponseOptions>() else {
+        return;
     };
-    let bill_number = caps["bill_number"]
-        .parse::<i32>()
-        .map_err(|_| FeedErrors::ParseError);
-    let bill_version = caps["bill_version"].to_string();
-    let title = caps["title"].to_string();
-    BillItem {
-        bill_type,
-        bill_number,
-        bill_version,
-        title,
+    if let Ok(value) = HeaderValue::from_str(&visit_cookie(chrono::Utc::now())) {
+        response.append_header(SET_COOKIE, value);
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct BillItem {
-    bill_type: Result<BillType, FeedErrors>,
-    bill_number: Result<i32, FeedErrors>,
-    bill_version: String,
-    title: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct BillItems {
-    bills: Vec<BillItem>,
+/// The `Set-Cookie` value remembering a visit. The path is `/` rather than `/feeds` because
+/// [`get_bills_feed`] is also called at `/api/...` when navigating on the client.
+#[cfg(feature = "ssr")]
+fn visit_cookie(now: chrono::DateTime<chrono::Utc>) -> String {
+    format!(
+        "{LAST_VISIT_COOKIE}={}; Path=/; Max-Age=31536000; SameSite=Lax; HttpOnly",
+        now.timestamp()
+    )
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "ssr"))]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_bill_type_parsing() {
-        let input = "H.R. 7261 (IH) - Reimagining Inclusive Arts Education Act";
+    fn test_last_visit_cookie() {
+        use http::header::{HeaderMap, HeaderValue, COOKIE};
+
+        let visited = chrono::DateTime::from_timestamp(1_707_200_000, 0).unwrap();
+        let cookie = visit_cookie(visited);
+        assert!(cookie.contains("; Path=/;"));
+
+        // The browser sends back the name and value only.
+        let (pair, _) = cookie.split_once(';').unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&format!("theme=dark; {pair}")).unwrap(),
+        );
+        assert_eq!(last_visit_in(&headers), Some(visited));
+        assert_eq!(last_visit_in(&HeaderMap::new()), None);
     }
 }
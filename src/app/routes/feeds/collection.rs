@@ -0,0 +1,198 @@
+use leptos::*;
+use leptos_router::{use_params, Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::routes::feeds::bills::BillsFeedCard;
+use crate::domain::bills::ordinal;
+use crate::domain::feeds::hearings::HearingItem;
+use crate::domain::feeds::issues::IssueItem;
+use crate::domain::feeds::laws::LawItem;
+use crate::domain::feeds::reports::ReportItem;
+use crate::domain::feeds::{Collection, FeedItem};
+
+/// Any GovInfo collection at `/feeds/:collection`. Bills have their own page at `/feeds/bills`.
+#[component]
+pub fn CollectionFeed() -> impl IntoView {
+    let params = use_params::<CollectionParams>();
+    let items = create_resource(
+        move || params.get().map(|p| p.collection).unwrap_or_default(),
+        |collection| async move { get_collection_feed(collection).await },
+    );
+
+    view! {
+        <div class="bg-slate-100">
+            <Transition fallback=move || view! { <p>"Loading..."</p> }>
+                {move || match items.get() {
+                    None => view! { <h1>"No items found in Feed."</h1> }.into_view(),
+                    Some(Err(error)) => view! { <p>"Error loading feed: "{error.to_string()}</p> }.into_view(),
+                    Some(Ok(items)) => view! {
+                        <h1>{items.collection.to_string()}</h1>
+                        {(items.rejected > 0).then(|| view! {
+                            <p class="m-4 text-sm text-gray-500">
+                                {items.rejected}" feed items could not be read and were skipped."
+                            </p>
+                        })}
+                        <ul>
+                            {items.entries
+                                .into_iter()
+                                .map(|entry| view! { <FeedItemCard entry/> })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+/// Picks the card of the item's collection.
+#[component]
+fn FeedItemCard(entry: CollectionEntry) -> impl IntoView {
+    let link = entry.link;
+    match entry.item {
+        FeedItem::Bill(bill) => view! { <BillsFeedCard bill is_new=false/> }.into_view(),
+        FeedItem::Law(law) => view! { <LawCard law link/> }.into_view(),
+        FeedItem::Record(issue) | FeedItem::FederalRegister(issue) => {
+            view! { <IssueCard issue link/> }.into_view()
+        }
+        FeedItem::Report(report) => view! { <ReportCard report link/> }.into_view(),
+        FeedItem::Hearing(hearing) => view! { <HearingCard hearing link/> }.into_view(),
+    }
+}
+
+#[component]
+fn FeedCard(heading: String, link: Option<String>, children: Children) -> impl IntoView {
+    view! {
+        <li class="m-4 p-4 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
+            <h5 class="mb-2 text-2xl font-bold tracking-tight text-gray-900 dark:text-white">{heading}</h5>
+            {children()}
+            {link.map(|link| view! {
+                <a href=link class="text-sm text-blue-700 hover:underline">"View on GovInfo"</a>
+            })}
+        </li>
+    }
+}
+
+#[component]
+fn LawCard(law: LawItem, link: Option<String>) -> impl IntoView {
+    view! {
+        <FeedCard heading=law.to_string() link>
+            <p class="mb-3 font-normal text-gray-700 dark:text-gray-400">{law.title}</p>
+        </FeedCard>
+    }
+}
+
+#[component]
+fn IssueCard(issue: IssueItem, link: Option<String>) -> impl IntoView {
+    let heading = issue.date.format("%A, %B %-d, %Y").to_string();
+    let number = match (issue.volume, issue.issue) {
+        (Some(volume), Some(number)) => Some(format!("Volume {volume}, Issue {number}")),
+        _ => None,
+    };
+    view! {
+        <FeedCard heading link>
+            {number.map(|number| view! { <p class="text-sm text-gray-500">{number}</p> })}
+            <p class="mb-3 font-normal text-gray-700 dark:text-gray-400">{issue.title}</p>
+        </FeedCard>
+    }
+}
+
+#[component]
+fn ReportCard(report: ReportItem, link: Option<String>) -> impl IntoView {
+    view! {
+        <FeedCard heading=report.to_string() link>
+            <p class="mb-3 font-normal text-gray-700 dark:text-gray-400">{report.title}</p>
+        </FeedCard>
+    }
+}
+
+#[component]
+fn HearingCard(hearing: HearingItem, link: Option<String>) -> impl IntoView {
+    let heading = format!(
+        "{} hearing ({})",
+        hearing.chamber,
+        ordinal(hearing.congress)
+    );
+    view! {
+        <FeedCard heading link>
+            <p class="mb-3 font-normal text-gray-700 dark:text-gray-400">{hearing.title}</p>
+        </FeedCard>
+    }
+}
+
+#[derive(Params, PartialEq, Eq, Clone)]
+struct CollectionParams {
+    collection: String,
+}
+
+/// Feed items shown on the page, newest first.
+#[cfg(feature = "ssr")]
+const FEED_LIMIT: usize = 200;
+
+/// Reads the items the poller stored, bills from `feed_item` and every other collection from
+/// `collection_item`. With background polling off the feed is polled here when it is due.
+#[server]
+async fn get_collection_feed(collection: String) -> Result<CollectionItems, ServerFnError> {
+    use crate::config::PollerSettings;
+    use crate::db::db;
+    use crate::domain::feeds::bills::FeedEntry;
+    use crate::poller;
+
+    let collection: Collection = collection.parse()?;
+    let db = db()?;
+    if let Some(settings) = use_context::<PollerSettings>() {
+        poller::poll_on_request(&db, &settings, collection).await?;
+    }
+    let rejected: Option<usize> = db
+        .query(
+            "SELECT VALUE rejected FROM feed_fetch WHERE source = $source AND status = 200 \
+             ORDER BY fetched_at DESC LIMIT 1",
+        )
+        .bind(("source", collection.slug()))
+        .await?
+        .take(0)?;
+
+    let entries = if collection == Collection::Bills {
+        let bills: Vec<FeedEntry> = db
+            .query("SELECT * FROM feed_item ORDER BY first_seen DESC LIMIT $limit")
+            .bind(("limit", FEED_LIMIT))
+            .await?
+            .take(0)?;
+        bills
+            .into_iter()
+            .map(|entry| CollectionEntry {
+                item: FeedItem::Bill(entry.item),
+                link: entry.link,
+            })
+            .collect()
+    } else {
+        db.query(
+            "SELECT item, link, first_seen FROM collection_item WHERE source = $source \
+             ORDER BY first_seen DESC LIMIT $limit",
+        )
+        .bind(("source", collection.slug()))
+        .bind(("limit", FEED_LIMIT))
+        .await?
+        .take(0)?
+    };
+
+    Ok(CollectionItems {
+        collection,
+        entries,
+        rejected: rejected.unwrap_or_default(),
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CollectionItems {
+    collection: Collection,
+    entries: Vec<CollectionEntry>,
+    /// Items the collection's parser could not read in the latest fetch.
+    rejected: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CollectionEntry {
+    item: FeedItem,
+    link: Option<String>,
+}
@@ -1,11 +1,26 @@
 use leptos::*;
 
+use crate::domain::feeds::Collection;
+
 #[component]
 pub fn FeedsPage() -> impl IntoView {
     view! {
         <div class="bg-slate-100">
             <h1>"Feeds Page"</h1>
-            <a href="feeds/bills">"Bills"</a>
+            <ul>
+                {Collection::ALL
+                    .into_iter()
+                    .map(|collection| view! {
+                        <li class="m-4 p-4 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
+                            <a class="text-xl font-bold text-blue-700 hover:underline" href=format!("/feeds/{}", collection.slug())>
+                                {collection.to_string()}
+                            </a>
+                            <span class="ms-2 text-sm text-gray-500">{collection.code()}</span>
+                            <p class="text-gray-700 dark:text-gray-400">{collection.description()}</p>
+                        </li>
+                    })
+                    .collect_view()}
+            </ul>
         </div>
     }
 }
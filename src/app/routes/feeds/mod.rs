@@ -2,6 +2,7 @@ use leptos::*;
 use leptos_router::Outlet;
 
 pub mod bills;
+pub mod collection;
 pub mod main;
 
 #[component]
@@ -0,0 +1,132 @@
+use leptos::*;
+use leptos_router::{use_query, Form, Params, ParamsError, ParamsMap};
+
+use crate::app::components::api_key::ServerErrorMessage;
+use crate::domain::members::{member_url, Member, MemberListQuery};
+
+pub mod member;
+
+/// Current members of Congress at `/members`, filtered by `?state=&chamber=`.
+#[component]
+pub fn MembersPage() -> impl IntoView {
+    let query = use_query::<MemberListQuery>();
+    let query = move || query.get().unwrap_or_default();
+    let members = create_resource(query, |query| async move { get_members(query).await });
+
+    view! {
+        <div class="bg-slate-100">
+            <h1>"Members"</h1>
+            <MemberFilters query=Signal::derive(query)/>
+            <Transition fallback=move || view! { <p>"Loading..."</p> }>
+                {move || members.get().map(|members| match members {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(members) if members.is_empty() => view! { <p class="m-4">"No members were found."</p> }.into_view(),
+                    Ok(members) => view! {
+                        <table class="m-4 text-sm text-left text-gray-700 bg-white rounded-lg shadow dark:bg-gray-800 dark:text-gray-400">
+                            <thead class="text-xs uppercase bg-gray-50 dark:bg-gray-700">
+                                <tr>
+                                    <th class="px-4 py-2">"Name"</th>
+                                    <th class="px-4 py-2">"Party"</th>
+                                    <th class="px-4 py-2">"State"</th>
+                                    <th class="px-4 py-2">"District"</th>
+                                    <th class="px-4 py-2">"Chamber"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {members.into_iter().map(|member| view! { <MemberRow member/> }).collect_view()}
+                            </tbody>
+                        </table>
+                    }.into_view(),
+                })}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn MemberFilters(#[prop(into)] query: Signal<MemberListQuery>) -> impl IntoView {
+    let state = move || query.get().state.unwrap_or_default();
+    let chamber = move || query.get().chamber.unwrap_or_default();
+
+    view! {
+        <Form method="GET" action="/members" class="flex flex-wrap items-end gap-4 m-4">
+            <label class="text-sm text-gray-700">
+                "State"
+                <input type="text" name="state" placeholder="Any"
+                    class="block w-48 p-2 border border-gray-300 rounded-lg"
+                    value=state prop:value=state/>
+            </label>
+            <label class="text-sm text-gray-700">
+                "Chamber"
+                <select name="chamber" class="block p-2 border border-gray-300 rounded-lg" prop:value=chamber>
+                    <option value="" selected=move || chamber().is_empty()>"Both"</option>
+                    <option value="house" selected=move || chamber() == "house">"House"</option>
+                    <option value="senate" selected=move || chamber() == "senate">"Senate"</option>
+                </select>
+            </label>
+            <input type="submit" value="Filter" class="px-3 py-2 text-sm font-medium text-white bg-blue-700 rounded-lg hover:bg-blue-800"/>
+        </Form>
+    }
+}
+
+#[component]
+fn MemberRow(member: Member) -> impl IntoView {
+    let chamber = member.chamber().map(str::to_string);
+
+    view! {
+        <tr class="border-b dark:border-gray-700">
+            <td class="px-4 py-2">
+                <a class="text-blue-700 hover:underline" href=member_url(&member.bioguide_id)>{member.name}</a>
+            </td>
+            <td class="px-4 py-2">{member.party_name}</td>
+            <td class="px-4 py-2">{member.state}</td>
+            <td class="px-4 py-2">{member.district}</td>
+            <td class="px-4 py-2">{chamber}</td>
+        </tr>
+    }
+}
+
+impl Params for MemberListQuery {
+    fn from_map(map: &ParamsMap) -> Result<Self, ParamsError> {
+        Ok(Self::from_params(|key| map.get(key).map(String::as_str)))
+    }
+}
+
+/// Members from the `member` table kept by the background sync. Until a member sync succeeded,
+/// the list is synced here first, since opened member pages store single members too.
+#[server]
+async fn get_members(query: MemberListQuery) -> Result<Vec<Member>, ServerFnError> {
+    use crate::congress::client;
+    use crate::db::db;
+    use crate::domain::sync::SyncState;
+    use crate::ingest::{status, sync_members, MEMBERS};
+    use leptos::logging::warn;
+
+    let db = db()?;
+    let last_sync = status(&db, MEMBERS).await?.map(|status| status.state);
+    let synced = match last_sync {
+        Some(SyncState::Ok | SyncState::Running) => Ok(()),
+        _ => match client() {
+            Ok(client) => sync_members(&db, &client)
+                .await
+                .map(|_| ())
+                .map_err(ServerFnError::from),
+            Err(error) => Err(error),
+        },
+    };
+
+    let mut members: Vec<Member> = db
+        .query("SELECT * FROM member ORDER BY state, name")
+        .await?
+        .take(0)?;
+    if let Err(error) = synced {
+        if members.is_empty() {
+            return Err(error);
+        }
+        warn!("listing stored members, the member sync failed: {error}");
+    }
+
+    // Members opened from a bill page are stored too, including former ones.
+    members.retain(|member| member.is_current() && query.matches(member));
+    Ok(members)
+}
@@ -0,0 +1,343 @@
+use leptos::*;
+use leptos_router::{use_params, Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::components::api_key::ServerErrorMessage;
+use crate::app::routes::bills::bill::{Field, Section};
+use crate::domain::api_key::is_api_key_missing;
+use crate::domain::bills::ordinal;
+use crate::domain::graph::{network_layout, party_color, Collaborator};
+use crate::domain::members::{member_url, MemberDetail, MemberLegislation};
+use crate::error_template::NotFound;
+
+/// Sponsored and cosponsored bills listed on a member's page, most recent first.
+const LEGISLATION_LIMIT: u32 = 50;
+
+/// Collaborators drawn in the network view.
+const NETWORK_LIMIT: usize = 12;
+
+/// Width and height of the network view.
+const NETWORK_SIZE: f64 = 400.0;
+
+#[component]
+pub fn MemberPage() -> impl IntoView {
+    let params = use_params::<MemberParams>();
+
+    move || match params.get() {
+        Ok(params) => view! { <MemberSections bioguide_id=params.bioguide_id/> }.into_view(),
+        Err(_) => view! { <NotFound/> }.into_view(),
+    }
+}
+
+#[component]
+fn MemberSections(bioguide_id: String) -> impl IntoView {
+    let source = bioguide_id.clone();
+    let member = create_resource(
+        move || source.clone(),
+        |bioguide_id| async move { get_member(bioguide_id).await },
+    );
+
+    view! {
+        <div class="bg-slate-100">
+            <Transition fallback=move || view! { <p>"Loading..."</p> }>
+                {move || member.get().map(|member| match member {
+                    Err(error) if is_api_key_missing(&error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Err(e) => view! { <h1>"Error loading member: "{e.to_string()}</h1> }.into_view(),
+                    Ok(member) => view! { <MemberOverview member/> }.into_view(),
+                })}
+            </Transition>
+            <NetworkSection bioguide_id=bioguide_id.clone()/>
+            <LegislationSection bioguide_id=bioguide_id.clone() title="Sponsored legislation" cosponsored=false/>
+            <LegislationSection bioguide_id title="Cosponsored legislation" cosponsored=true/>
+        </div>
+    }
+}
+
+#[component]
+fn MemberOverview(member: MemberDetail) -> impl IntoView {
+    let name = member.name();
+    let party = member.party().map(|party| party.party_name.clone());
+    let terms = member
+        .terms_by_recency()
+        .into_iter()
+        .map(|term| {
+            let place = match term.district {
+                Some(district) => format!("{}, district {district}", term.state_name.clone().unwrap_or_default()),
+                None => term.state_name.clone().unwrap_or_default(),
+            };
+            view! {
+                <li>
+                    {term.congress.map(|congress| format!("{} Congress, ", ordinal(congress)))}
+                    {term.member_type.clone().unwrap_or_else(|| term.chamber.clone())}" - "{place}" ("{term.years()}")"
+                </li>
+            }
+        })
+        .collect_view();
+    let party_history = member
+        .party_history
+        .iter()
+        .map(|affiliation| {
+            let years = format!(
+                "{}–{}",
+                affiliation
+                    .start_year
+                    .map(|year| year.to_string())
+                    .unwrap_or_default(),
+                affiliation
+                    .end_year
+                    .map(|year| year.to_string())
+                    .unwrap_or_default(),
+            );
+            view! { <p>{affiliation.party_name.clone()}" ("{years}")"</p> }
+        })
+        .collect_view();
+
+    view! {
+        <Section title="Overview">
+            <div class="flex gap-6">
+                {member.depiction.map(|depiction| view! {
+                    <img src=depiction.image_url alt=name.clone() class="w-32 h-auto rounded"/>
+                })}
+                <div>
+                    <h1 class="mb-1 text-2xl font-bold text-gray-900 dark:text-white">{member.honorific_name.map(|honorific| format!("{honorific} "))}{name.clone()}</h1>
+                    <dl>
+                        <Field label="Party">{party}</Field>
+                        <Field label="State">{member.state}{member.district.map(|district| format!(", district {district}"))}</Field>
+                        <Field label="Born">{member.birth_year}</Field>
+                        <Field label="Party history">{party_history}</Field>
+                        {member.official_website_url.map(|url| view! {
+                            <Field label="Website"><a class="text-blue-700 hover:underline" href=url.clone()>{url}</a></Field>
+                        })}
+                    </dl>
+                </div>
+            </div>
+        </Section>
+        <Section title="Terms">
+            <ul>{terms}</ul>
+        </Section>
+    }
+}
+
+/// The member in the middle, linked to the members who most often cosponsor with them. Thicker
+/// lines mean more shared bills.
+#[component]
+fn NetworkSection(bioguide_id: String) -> impl IntoView {
+    let network = create_resource(
+        move || bioguide_id.clone(),
+        |bioguide_id| async move { get_cosponsor_network(bioguide_id).await },
+    );
+
+    view! {
+        <Section title="Cosponsorship network">
+            <Transition fallback=move || view! { <p>"Loading network..."</p> }>
+                {move || network.get().map(|network| match network {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(network) if network.collaborators.is_empty() => view! {
+                        <p>"No cosponsorships stored yet. They are filled in as the background sync indexes bills."</p>
+                    }.into_view(),
+                    Ok(network) => view! { <CosponsorNetwork network/> }.into_view(),
+                })}
+            </Transition>
+        </Section>
+    }
+}
+
+#[component]
+fn CosponsorNetwork(network: Network) -> impl IntoView {
+    let center = NETWORK_SIZE / 2.0;
+    let nodes = network_layout(&network.collaborators, NETWORK_SIZE);
+    let edges = nodes
+        .iter()
+        .map(|node| view! {
+            <line x1=center y1=center x2=node.x y2=node.y stroke="#9ca3af"
+                stroke-width={1.0 + 5.0 * node.weight} stroke-opacity={0.3 + 0.7 * node.weight}/>
+        })
+        .collect_view();
+    let collaborators = nodes
+        .into_iter()
+        .map(|node| {
+            let title = format!("{} - {} shared bills", node.collaborator.label(), node.collaborator.shared);
+            view! {
+                <a href=member_url(&node.collaborator.bioguide_id)>
+                    <circle cx=node.x cy=node.y r="10" fill=party_color(node.collaborator.party.as_deref())>
+                        <title>{title}</title>
+                    </circle>
+                    <text x=node.x y={node.y + 22.0} text-anchor="middle" font-size="10" fill="#374151">
+                        {node.collaborator.label().to_string()}
+                    </text>
+                </a>
+            }
+        })
+        .collect_view();
+    let viewbox = format!("0 0 {NETWORK_SIZE} {NETWORK_SIZE}");
+
+    view! {
+        <svg viewBox=viewbox class="w-full max-w-xl">
+            {edges}
+            {collaborators}
+            <circle cx=center cy=center r="16" fill=party_color(network.party.as_deref())/>
+        </svg>
+    }
+}
+
+#[component]
+fn LegislationSection(
+    bioguide_id: String,
+    title: &'static str,
+    cosponsored: bool,
+) -> impl IntoView {
+    let legislation = create_resource(
+        move || bioguide_id.clone(),
+        move |bioguide_id| async move { get_member_legislation(bioguide_id, cosponsored).await },
+    );
+
+    view! {
+        <Section title>
+            <Transition fallback=move || view! { <p>"Loading legislation..."</p> }>
+                {move || legislation.get().map(|legislation| match legislation {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(legislation) if legislation.items.is_empty() => view! { <p>"None."</p> }.into_view(),
+                    Ok(legislation) => {
+                        let shown = legislation.items.len();
+                        view! {
+                            <p class="mb-2 text-sm text-gray-500">{shown}" most recent of "{legislation.total}</p>
+                            <ul>
+                                {legislation.items.into_iter().map(|item| view! { <LegislationItem item/> }).collect_view()}
+                            </ul>
+                        }.into_view()
+                    }
+                })}
+            </Transition>
+        </Section>
+    }
+}
+
+#[component]
+fn LegislationItem(item: MemberLegislation) -> impl IntoView {
+    let citation = match (&item.bill_type, &item.number, &item.amendment_number) {
+        (Some(bill_type), Some(number), _) => format!("{bill_type} {number}"),
+        (_, _, Some(amendment)) => format!("Amendment {amendment}"),
+        _ => String::new(),
+    };
+    let citation = match item.id() {
+        Some(id) => view! { <a class="text-blue-700 hover:underline" href=id.url()>{citation}</a> }
+            .into_view(),
+        None => citation.into_view(),
+    };
+
+    view! {
+        <li class="mb-2">
+            {citation}
+            {item.congress.map(|congress| format!(" ({} Congress)", ordinal(congress)))}
+            " "{item.title}
+            <p class="text-sm text-gray-500">
+                {item.introduced_date.map(|date| format!("Introduced {date}"))}
+                {item.latest_action.map(|action| format!(" - {}: {}", action.action_date, action.text))}
+            </p>
+        </li>
+    }
+}
+
+#[derive(Params, PartialEq, Eq, Clone)]
+pub struct MemberParams {
+    bioguide_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Network {
+    /// Party of the member in the middle.
+    party: Option<String>,
+    collaborators: Vec<Collaborator>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Legislation {
+    items: Vec<MemberLegislation>,
+    /// All the member's sponsored or cosponsored legislation, not only the listed items.
+    total: u32,
+}
+
+#[server]
+async fn get_member(bioguide_id: String) -> Result<MemberDetail, ServerFnError> {
+    use crate::congress::client;
+    use crate::db::db;
+    use crate::domain::members::{Member, MEMBER_TABLE};
+    use crate::ingest::store_member;
+    use leptos::logging::warn;
+
+    #[derive(Deserialize)]
+    struct Stored {
+        #[serde(rename = "updateDate")]
+        update_date: Option<String>,
+        detail: Option<MemberDetail>,
+    }
+
+    let db = db()?;
+
+    // Like bills, the member sync updates `updateDate`, so a detail with the same date is current.
+    let stored: Option<Stored> = db.select((MEMBER_TABLE, bioguide_id.as_str())).await?;
+    let stored = match stored {
+        Some(Stored {
+            update_date,
+            detail: Some(detail),
+        }) if detail.update_date == update_date => return Ok(detail),
+        Some(stored) => stored.detail,
+        None => None,
+    };
+
+    let fetched = match client() {
+        Ok(client) => client
+            .member(&bioguide_id)
+            .await
+            .map_err(ServerFnError::from),
+        Err(error) => Err(error),
+    };
+    match (fetched, stored) {
+        (Ok(member), _) => {
+            store_member(&db, &Member::from(&member), Some(&member)).await?;
+            Ok(member)
+        }
+        (Err(error), Some(stored)) => {
+            warn!("serving stored member {bioguide_id}, Congress.gov failed: {error}");
+            Ok(stored)
+        }
+        (Err(error), None) => Err(error),
+    }
+}
+
+#[server]
+async fn get_member_legislation(
+    bioguide_id: String,
+    cosponsored: bool,
+) -> Result<Legislation, ServerFnError> {
+    use crate::congress::client;
+    let client = client()?;
+
+    let page = if cosponsored {
+        client
+            .member_cosponsored_legislation(&bioguide_id, LEGISLATION_LIMIT)
+            .await?
+    } else {
+        client
+            .member_sponsored_legislation(&bioguide_id, LEGISLATION_LIMIT)
+            .await?
+    };
+    Ok(Legislation {
+        items: page.items,
+        total: page.count,
+    })
+}
+
+#[server]
+async fn get_cosponsor_network(bioguide_id: String) -> Result<Network, ServerFnError> {
+    use crate::db::db;
+    use crate::domain::members::{Member, MEMBER_TABLE};
+    use crate::graph::frequent_cosponsors;
+
+    let db = db()?;
+    let member: Option<Member> = db.select((MEMBER_TABLE, bioguide_id.as_str())).await?;
+    Ok(Network {
+        party: member.and_then(|member| member.party_name),
+        collaborators: frequent_cosponsors(&db, &bioguide_id, NETWORK_LIMIT).await?,
+    })
+}
@@ -1,4 +1,7 @@
+pub mod admin;
 pub mod bills;
 pub mod feeds;
 pub mod home;
 pub mod maps;
+pub mod members;
+pub mod search;
@@ -0,0 +1,208 @@
+use leptos::*;
+use leptos_router::{use_query, Form, Params, ParamsError, ParamsMap};
+
+use crate::app::components::api_key::ServerErrorMessage;
+use crate::domain::bills::{ordinal, BillType, LegislativeStage};
+use crate::domain::search::query::{QueryError, SearchQuery};
+use crate::domain::search::{fragments, snippet, Fragment, SearchHit, SearchParams};
+
+/// Characters of summary shown around the first match.
+const SNIPPET_RADIUS: usize = 120;
+
+/// Full-text search over stored bills at `/search?q=...&congress=&type=&stage=`.
+///
+/// The search box is parsed on both ends, so syntax errors show without a request.
+#[component]
+pub fn SearchPage() -> impl IntoView {
+    let params = use_query::<SearchParams>();
+    let params = move || params.get().unwrap_or_default();
+    let query = move || params().query();
+    let hits = create_resource(
+        move || query().ok(),
+        |query| async move {
+            match query {
+                Some(query) => search_bills(query).await,
+                None => Ok(Vec::new()),
+            }
+        },
+    );
+
+    view! {
+        <div class="bg-slate-100">
+            <h1>"Search Bills"</h1>
+            <SearchForm params=Signal::derive(params)/>
+            {move || query().err().map(|errors| view! { <QueryErrors input=params().q errors/> })}
+            <Transition fallback=move || view! { <p>"Searching..."</p> }>
+                {move || hits.get().map(|hits| match hits {
+                    Err(error) => view! { <ServerErrorMessage error/> }.into_view(),
+                    Ok(_) if query().is_err() => ().into_view(),
+                    Ok(_) if query().is_ok_and(|query| query.is_empty()) => view! {
+                        <p class="m-4 text-gray-500">
+                            "Search titles, summaries and subjects of stored bills. Narrow it down with "
+                            <code>"type:hr congress:118 sponsor:\"Smith\" stage:reported version:enr"</code>
+                            ", and quote phrases like "<code>"\"clean energy\""</code>"."
+                        </p>
+                    }.into_view(),
+                    Ok(hits) if hits.is_empty() => view! { <p class="m-4">"No bills matched."</p> }.into_view(),
+                    Ok(hits) => view! {
+                        <ul>
+                            {hits.into_iter().map(|hit| view! { <li><SearchResult hit/></li> }).collect_view()}
+                        </ul>
+                    }.into_view(),
+                })}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn SearchForm(#[prop(into)] params: Signal<SearchParams>) -> impl IntoView {
+    let q = move || params.get().q;
+    let congress = move || {
+        params
+            .get()
+            .congress
+            .map(|congress| congress.to_string())
+            .unwrap_or_default()
+    };
+    let bill_type = move || {
+        params
+            .get()
+            .bill_type
+            .map(|bill_type| bill_type.slug())
+            .unwrap_or_default()
+    };
+    let stage = move || {
+        params
+            .get()
+            .stage
+            .map(|stage| stage.code())
+            .unwrap_or_default()
+    };
+
+    view! {
+        <Form method="GET" action="/search" class="flex flex-wrap items-end gap-4 m-4">
+            <label class="text-sm text-gray-700">
+                "Words"
+                <input type="search" name="q" placeholder="type:hr \"clean energy\""
+                    class="block w-96 p-2 font-mono border border-gray-300 rounded-lg"
+                    value=q prop:value=q/>
+            </label>
+            <label class="text-sm text-gray-700">
+                "Congress"
+                <input type="number" name="congress" min="1" placeholder="Any"
+                    class="block w-24 p-2 border border-gray-300 rounded-lg"
+                    value=congress prop:value=congress/>
+            </label>
+            <label class="text-sm text-gray-700">
+                "Type"
+                <select name="type" class="block p-2 border border-gray-300 rounded-lg" prop:value=bill_type>
+                    <option value="" selected=move || bill_type().is_empty()>"Any"</option>
+                    {BillType::ALL
+                        .into_iter()
+                        .map(|option| view! {
+                            <option value=option.slug() selected=move || bill_type() == option.slug()>
+                                {option.citation()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <label class="text-sm text-gray-700">
+                "Stage"
+                <select name="stage" class="block p-2 border border-gray-300 rounded-lg" prop:value=stage>
+                    <option value="" selected=move || stage().is_empty()>"Any"</option>
+                    {LegislativeStage::ALL
+                        .into_iter()
+                        .map(|option| view! {
+                            <option value=option.code() selected=move || stage() == option.code()>
+                                {option.to_string()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <input type="submit" value="Search" class="px-3 py-2 text-sm font-medium text-white bg-blue-700 rounded-lg hover:bg-blue-800"/>
+        </Form>
+    }
+}
+
+/// The search box input with each error underlined, followed by the messages.
+#[component]
+fn QueryErrors(input: String, errors: Vec<QueryError>) -> impl IntoView {
+    let mut marked = Vec::new();
+    let mut pos = 0;
+    for error in &errors {
+        let span = error.span.start.max(pos)..error.span.end.min(input.len());
+        if span.start >= span.end {
+            continue;
+        }
+        marked.push(input[pos..span.start].to_string().into_view());
+        marked.push(
+            view! { <span class="underline decoration-wavy decoration-red-600">{input[span.clone()].to_string()}</span> }
+                .into_view(),
+        );
+        pos = span.end;
+    }
+    marked.push(input[pos..].to_string().into_view());
+
+    view! {
+        <div class="m-4 p-4 text-sm bg-red-50 border border-red-200 rounded-lg">
+            <pre class="mb-2 font-mono whitespace-pre-wrap">{marked}</pre>
+            <ul class="text-red-700">
+                {errors.into_iter().map(|error| view! { <li>{error.message}</li> }).collect_view()}
+            </ul>
+        </div>
+    }
+}
+
+#[component]
+fn SearchResult(hit: SearchHit) -> impl IntoView {
+    let href = hit.id().map(|id| id.url());
+    let summary = hit
+        .summary
+        .as_deref()
+        .map(|summary| view! { <p class="mb-2 text-sm text-gray-700"><Highlighted fragments=snippet(summary, SNIPPET_RADIUS)/></p> });
+
+    view! {
+        <div class="m-4 p-6 bg-white border border-gray-200 rounded-lg shadow dark:bg-gray-800 dark:border-gray-700">
+            <p class="text-sm text-gray-500">
+                {hit.bill_type.citation()}" "{hit.number.clone()}" ("{ordinal(hit.congress)}")"
+                {hit.stage.map(|stage| view! { <span class="ms-2 px-2.5 py-0.5 text-xs font-medium rounded bg-gray-100 text-gray-800">{stage}</span> })}
+            </p>
+            <a href=href class="block mb-2 text-xl font-bold text-gray-900 hover:underline dark:text-white">
+                <Highlighted fragments=fragments(&hit.title)/>
+            </a>
+            {summary}
+        </div>
+    }
+}
+
+/// Text with the matched terms in `<mark>`.
+#[component]
+fn Highlighted(fragments: Vec<Fragment>) -> impl IntoView {
+    fragments
+        .into_iter()
+        .map(|fragment| {
+            if fragment.matched {
+                view! { <mark class="bg-yellow-200">{fragment.text}</mark> }.into_view()
+            } else {
+                fragment.text.into_view()
+            }
+        })
+        .collect_view()
+}
+
+impl Params for SearchParams {
+    fn from_map(map: &ParamsMap) -> Result<Self, ParamsError> {
+        Ok(Self::from_params(|key| map.get(key).map(String::as_str)))
+    }
+}
+
+#[server]
+async fn search_bills(query: SearchQuery) -> Result<Vec<SearchHit>, ServerFnError> {
+    use crate::db::db;
+
+    let db = db()?;
+    Ok(crate::search::search_bills(&db, &query).await?)
+}
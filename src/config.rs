@@ -0,0 +1,642 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// File read when `APP_CONFIG` is not set. It is optional, everything can come from the environment.
+const DEFAULT_CONFIG_FILE: &str = "settings.toml";
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Could not read config file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not parse config file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("Unknown SurrealDB auth mode `{0}`, expected one of root, namespace, database, scope")]
+    InvalidAuthMode(String),
+    #[error("`database.{field}` must not be empty")]
+    Empty { field: &'static str },
+    #[error("`database.{field}` is required when signing in with the `{auth}` auth mode")]
+    Missing { field: &'static str, auth: AuthMode },
+    #[error("`{key}` must be `true` or `false`, got `{value}`")]
+    InvalidBool { key: &'static str, value: String },
+    #[error("`{key}` must be a positive number, got `{value}`")]
+    InvalidNumber { key: &'static str, value: String },
+    #[error("`site.public_url` must be an http:// or https:// URL, got `{0}`")]
+    InvalidPublicUrl(String),
+    #[error("Endpoint `{endpoint}` needs the `{feature}` cargo feature")]
+    EngineDisabled {
+        endpoint: String,
+        feature: &'static str,
+    },
+}
+
+/// Typed application settings, read from an optional TOML file and then overridden by env vars.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub database: DatabaseSettings,
+    pub migrations: MigrationSettings,
+    pub congress: CongressSettings,
+    pub ingest: IngestSettings,
+    pub poller: PollerSettings,
+    pub site: SiteSettings,
+}
+
+impl Settings {
+    /// Loads `APP_CONFIG` (or `settings.toml` if present), applies env overrides and validates the result.
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = std::env::var_os("APP_CONFIG").map(PathBuf::from);
+        let mut settings = match path {
+            Some(path) => Self::from_file(&path)?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                Self::from_file(Path::new(DEFAULT_CONFIG_FILE))?
+            }
+            None => Self::default(),
+        };
+        settings.apply_env(|key| std::env::var(key).ok())?;
+        settings.congress.read_key_file()?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_owned(),
+            source,
+        })?;
+        toml::from_str(&content).map_err(|source| ConfigError::Parse {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        let db = &mut self.database;
+        if let Some(endpoint) = var("SURREAL_ENDPOINT") {
+            db.endpoint = endpoint;
+        }
+        if let Some(auth) = var("SURREAL_AUTH") {
+            db.auth = auth.parse()?;
+        }
+        if let Some(username) = var("SURREAL_USER") {
+            db.username = Some(username);
+        }
+        if let Some(password) = var("SURREAL_PASS") {
+            db.password = Some(password);
+        }
+        if let Some(namespace) = var("SURREAL_NS") {
+            db.namespace = namespace;
+        }
+        if let Some(database) = var("SURREAL_DB") {
+            db.database = database;
+        }
+        if let Some(scope) = var("SURREAL_SCOPE") {
+            db.scope = Some(scope);
+        }
+
+        let migrations = &mut self.migrations;
+        if let Some(value) = var("MIGRATIONS_AUTO_APPLY") {
+            migrations.auto_apply = parse_bool("MIGRATIONS_AUTO_APPLY", value)?;
+        }
+        if let Some(value) = var("MIGRATIONS_DRY_RUN") {
+            migrations.dry_run = parse_bool("MIGRATIONS_DRY_RUN", value)?;
+        }
+
+        let congress = &mut self.congress;
+        if let Some(api_key) = var("CONGRESS_GOV_API_TOKEN") {
+            congress.api_key = Some(api_key);
+        }
+        if let Some(path) = var("CONGRESS_GOV_API_TOKEN_FILE") {
+            congress.api_key_file = Some(PathBuf::from(path));
+        }
+
+        let ingest = &mut self.ingest;
+        if let Some(value) = var("INGEST_ENABLED") {
+            ingest.enabled = parse_bool("INGEST_ENABLED", value)?;
+        }
+        if let Some(value) = var("INGEST_INTERVAL_SECS") {
+            ingest.interval_secs = parse_number("INGEST_INTERVAL_SECS", value)?;
+        }
+        if let Some(value) = var("INGEST_LOOKBACK_DAYS") {
+            ingest.lookback_days = parse_number("INGEST_LOOKBACK_DAYS", value)?;
+        }
+
+        let poller = &mut self.poller;
+        if let Some(value) = var("POLLER_ENABLED") {
+            poller.enabled = parse_bool("POLLER_ENABLED", value)?;
+        }
+        if let Some(value) = var("POLLER_INTERVAL_MINUTES") {
+            poller.interval_minutes = parse_number("POLLER_INTERVAL_MINUTES", value)?;
+        }
+        if let Some(value) = var("POLLER_MAX_BACKOFF_MINUTES") {
+            poller.max_backoff_minutes = parse_number("POLLER_MAX_BACKOFF_MINUTES", value)?;
+        }
+        if let Some(value) = var("POLLER_TIMEOUT_SECS") {
+            poller.timeout_secs = parse_number("POLLER_TIMEOUT_SECS", value)?;
+        }
+
+        if let Some(public_url) = var("SITE_PUBLIC_URL") {
+            self.site.public_url = public_url;
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.database.validate()?;
+        positive("ingest.interval_secs", self.ingest.interval_secs)?;
+        positive("ingest.lookback_days", self.ingest.lookback_days.into())?;
+        positive(
+            "poller.interval_minutes",
+            self.poller.interval_minutes.into(),
+        )?;
+        positive(
+            "poller.max_backoff_minutes",
+            self.poller.max_backoff_minutes.into(),
+        )?;
+        positive("poller.timeout_secs", self.poller.timeout_secs)?;
+        self.site.validate()
+    }
+}
+
+fn parse_bool(key: &'static str, value: String) -> Result<bool, ConfigError> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(ConfigError::InvalidBool { key, value }),
+    }
+}
+
+fn parse_number<T>(key: &'static str, value: String) -> Result<T, ConfigError>
+where
+    T: std::str::FromStr + Default + PartialEq,
+{
+    match value.trim().parse() {
+        Ok(number) if number != T::default() => Ok(number),
+        _ => Err(ConfigError::InvalidNumber { key, value }),
+    }
+}
+
+/// Rejects a zero read from the config file, like [`parse_number`] does for env vars.
+fn positive(key: &'static str, value: u64) -> Result<(), ConfigError> {
+    if value == 0 {
+        return Err(ConfigError::InvalidNumber {
+            key,
+            value: value.to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMode {
+    #[default]
+    Root,
+    Namespace,
+    Database,
+    Scope,
+}
+
+impl fmt::Display for AuthMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthMode::Root => write!(f, "root"),
+            AuthMode::Namespace => write!(f, "namespace"),
+            AuthMode::Database => write!(f, "database"),
+            AuthMode::Scope => write!(f, "scope"),
+        }
+    }
+}
+
+impl std::str::FromStr for AuthMode {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "root" => Ok(AuthMode::Root),
+            "namespace" | "ns" => Ok(AuthMode::Namespace),
+            "database" | "db" => Ok(AuthMode::Database),
+            "scope" | "sc" => Ok(AuthMode::Scope),
+            _ => Err(ConfigError::InvalidAuthMode(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct DatabaseSettings {
+    pub endpoint: String,
+    pub auth: AuthMode,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub namespace: String,
+    pub database: String,
+    /// Only used with [`AuthMode::Scope`].
+    pub scope: Option<String>,
+}
+
+/// Embedded builds default to their own engine so they run without any server.
+fn default_endpoint() -> &'static str {
+    if cfg!(feature = "kv-mem") {
+        "mem://"
+    } else if cfg!(feature = "kv-rocksdb") {
+        "rocksdb://data/congress.db"
+    } else {
+        "ws://127.0.0.1:8000"
+    }
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            endpoint: default_endpoint().to_string(),
+            auth: AuthMode::Root,
+            username: None,
+            password: None,
+            namespace: "test".to_string(),
+            database: "test".to_string(),
+            scope: None,
+        }
+    }
+}
+
+impl DatabaseSettings {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let required = |field: &'static str, value: &Option<String>| match value {
+            Some(value) if !value.is_empty() => Ok(()),
+            _ => Err(ConfigError::Missing {
+                field,
+                auth: self.auth,
+            }),
+        };
+
+        if self.endpoint.trim().is_empty() {
+            return Err(ConfigError::Empty { field: "endpoint" });
+        }
+        if self.namespace.trim().is_empty() {
+            return Err(ConfigError::Empty { field: "namespace" });
+        }
+        if self.database.trim().is_empty() {
+            return Err(ConfigError::Empty { field: "database" });
+        }
+        if let Some(feature) = self.missing_engine_feature() {
+            return Err(ConfigError::EngineDisabled {
+                endpoint: self.endpoint.clone(),
+                feature,
+            });
+        }
+        // Embedded engines run in-process without authentication.
+        if self.is_embedded() {
+            return Ok(());
+        }
+        required("username", &self.username)?;
+        required("password", &self.password)?;
+        if self.auth == AuthMode::Scope {
+            required("scope", &self.scope)?;
+        }
+        Ok(())
+    }
+
+    /// The endpoint with a `ws://` scheme added when none was given, as `engine::any` requires one.
+    pub fn address(&self) -> String {
+        if self.endpoint.contains("://") {
+            self.endpoint.clone()
+        } else {
+            format!("ws://{}", self.endpoint)
+        }
+    }
+
+    fn scheme(&self) -> &str {
+        self.endpoint
+            .split_once("://")
+            .map(|(scheme, _)| scheme)
+            .unwrap_or("ws")
+    }
+
+    /// Whether the endpoint points at an in-process engine rather than a server.
+    pub fn is_embedded(&self) -> bool {
+        matches!(self.scheme(), "mem" | "memory" | "rocksdb" | "file")
+    }
+
+    fn missing_engine_feature(&self) -> Option<&'static str> {
+        match self.scheme() {
+            "mem" | "memory" if !cfg!(feature = "kv-mem") => Some("kv-mem"),
+            "rocksdb" | "file" if !cfg!(feature = "kv-rocksdb") => Some("kv-rocksdb"),
+            _ => None,
+        }
+    }
+
+    pub fn username(&self) -> &str {
+        self.username.as_deref().unwrap_or_default()
+    }
+
+    pub fn password(&self) -> &str {
+        self.password.as_deref().unwrap_or_default()
+    }
+}
+
+// Hand written so the password never ends up in logs through `AppState`'s Debug output.
+impl fmt::Debug for DatabaseSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseSettings")
+            .field("endpoint", &self.endpoint)
+            .field("auth", &self.auth)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .field("namespace", &self.namespace)
+            .field("database", &self.database)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MigrationSettings {
+    /// Apply pending migrations on startup.
+    pub auto_apply: bool,
+    /// Log the migration status report and exit without changing the database.
+    pub dry_run: bool,
+}
+
+impl Default for MigrationSettings {
+    fn default() -> Self {
+        Self {
+            auto_apply: true,
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CongressSettings {
+    pub api_key: Option<String>,
+    /// File holding the key, e.g. a mounted secret. Only read when `api_key` is not set.
+    pub api_key_file: Option<PathBuf>,
+}
+
+impl CongressSettings {
+    fn read_key_file(&mut self) -> Result<(), ConfigError> {
+        if self.api_key.is_some() {
+            return Ok(());
+        }
+        if let Some(path) = &self.api_key_file {
+            let key = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+                path: path.clone(),
+                source,
+            })?;
+            self.api_key = Some(key.trim().to_string());
+        }
+        Ok(())
+    }
+
+    /// The configured key, `None` when missing or blank.
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+    }
+}
+
+impl fmt::Debug for CongressSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CongressSettings")
+            .field("api_key", &self.api_key.as_ref().map(|_| "***"))
+            .field("api_key_file", &self.api_key_file)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct IngestSettings {
+    /// Run the background bill sync. It also needs an API key.
+    pub enabled: bool,
+    /// Seconds between sync runs.
+    pub interval_secs: u64,
+    /// How far back the first run reaches when there is no sync cursor yet.
+    pub lookback_days: u32,
+}
+
+impl Default for IngestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 15 * 60,
+            lookback_days: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PollerSettings {
+    /// Poll the GovInfo RSS feeds in the background.
+    pub enabled: bool,
+    /// Minutes between polls of a feed that sets no `<ttl>`. A longer `<ttl>` wins.
+    pub interval_minutes: u32,
+    /// Upper bound of the backoff after failed polls.
+    pub max_backoff_minutes: u32,
+    /// Seconds before a feed request is given up and counted as a failed poll.
+    pub timeout_secs: u64,
+}
+
+impl Default for PollerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 15,
+            max_backoff_minutes: 6 * 60,
+            timeout_secs: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SiteSettings {
+    /// Where readers reach the site, e.g. `https://congress.example.org`. Absolute links such as
+    /// the ones in the syndication feeds start with it, never with the request's `Host`.
+    pub public_url: String,
+}
+
+impl Default for SiteSettings {
+    fn default() -> Self {
+        Self {
+            public_url: "http://127.0.0.1:3000".to_string(),
+        }
+    }
+}
+
+impl SiteSettings {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let url = self.public_url.trim();
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .unwrap_or_default();
+        if rest.is_empty() || rest.contains(|c: char| c.is_whitespace() || "\"'<>".contains(c)) {
+            return Err(ConfigError::InvalidPublicUrl(self.public_url.clone()));
+        }
+        Ok(())
+    }
+
+    /// The public URL without a trailing slash, ready to have paths appended.
+    pub fn base_url(&self) -> &str {
+        self.public_url.trim().trim_end_matches('/')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_validate_file() {
+        let settings: Settings = toml::from_str(
+            r#"
+            [database]
+            endpoint = "wss://db.internal:8000"
+            auth = "database"
+            username = "app"
+            password = "secret"
+            namespace = "congress"
+            database = "prod"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.database.auth, AuthMode::Database);
+        assert_eq!(settings.database.namespace, "congress");
+        assert!(settings.validate().is_ok());
+
+        let mut zero = settings.clone();
+        zero.ingest.interval_secs = 0;
+        assert!(matches!(
+            zero.validate(),
+            Err(ConfigError::InvalidNumber {
+                key: "ingest.interval_secs",
+                ..
+            })
+        ));
+
+        for public_url in ["congress.example.org", "https://", "https://a.org/\"><x"] {
+            let site = SiteSettings {
+                public_url: public_url.to_string(),
+            };
+            assert!(matches!(
+                site.validate(),
+                Err(ConfigError::InvalidPublicUrl(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let path = std::env::temp_dir().join(format!("settings-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"
+            [database]
+            endpoint = "wss://db.internal:8000"
+            auth = "root"
+            namespace = "congress"
+
+            [ingest]
+            interval_secs = 900
+            "#,
+        )
+        .unwrap();
+        let mut settings = Settings::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        settings
+            .apply_env(|key| match key {
+                "SURREAL_ENDPOINT" => Some("ws://localhost:8000".to_string()),
+                "SURREAL_AUTH" => Some("scope".to_string()),
+                "SURREAL_USER" => Some("reader".to_string()),
+                "SURREAL_PASS" => Some("pw".to_string()),
+                "MIGRATIONS_DRY_RUN" => Some("yes".to_string()),
+                "CONGRESS_GOV_API_TOKEN" => Some("  ".to_string()),
+                "INGEST_INTERVAL_SECS" => Some("60".to_string()),
+                "SITE_PUBLIC_URL" => Some("https://congress.example.org/".to_string()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(settings.database.endpoint, "ws://localhost:8000");
+        assert_eq!(settings.database.auth, AuthMode::Scope);
+        // Values without an env var keep the file's.
+        assert_eq!(settings.database.namespace, "congress");
+        assert!(settings.migrations.dry_run);
+        assert_eq!(settings.congress.api_key(), None);
+        assert_eq!(settings.ingest.interval_secs, 60);
+        assert_eq!(settings.site.base_url(), "https://congress.example.org");
+        assert!(matches!(
+            settings.validate(),
+            Err(ConfigError::Missing { field: "scope", .. })
+        ));
+        assert!(matches!(
+            settings.apply_env(|key| (key == "INGEST_LOOKBACK_DAYS").then(|| "-1".to_string())),
+            Err(ConfigError::InvalidNumber { .. })
+        ));
+        assert!(matches!(
+            settings.apply_env(|key| (key == "POLLER_TIMEOUT_SECS").then(|| "0".to_string())),
+            Err(ConfigError::InvalidNumber {
+                key: "POLLER_TIMEOUT_SECS",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_embedded_endpoint() {
+        let settings = DatabaseSettings {
+            endpoint: "mem://".to_string(),
+            ..Default::default()
+        };
+
+        assert!(settings.is_embedded());
+        if cfg!(feature = "kv-mem") {
+            assert!(settings.validate().is_ok());
+        } else {
+            assert!(matches!(
+                settings.validate(),
+                Err(ConfigError::EngineDisabled {
+                    feature: "kv-mem",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_missing_credentials() {
+        let settings = DatabaseSettings {
+            endpoint: "127.0.0.1:8000".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(settings.address(), "ws://127.0.0.1:8000");
+        assert!(matches!(
+            settings.validate(),
+            Err(ConfigError::Missing {
+                field: "username",
+                auth: AuthMode::Root
+            })
+        ));
+        assert!(matches!(
+            "superuser".parse::<AuthMode>(),
+            Err(ConfigError::InvalidAuthMode(_))
+        ));
+    }
+}
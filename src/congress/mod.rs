@@ -0,0 +1,479 @@
+//! Typed client for the Congress.gov v3 API.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use leptos::{use_context, ServerFnError};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::domain::api_key::API_KEY_MISSING;
+use crate::domain::bills::{
+    BillAction, BillCommittee, BillDetail, BillId, BillListItem, BillSubjects, BillSummary,
+    Cosponsor, RelatedBill, TextVersion,
+};
+use crate::domain::committees::Committee;
+use crate::domain::members::{Member, MemberDetail, MemberLegislation};
+
+pub mod rate_limit;
+
+use rate_limit::RateLimiter;
+
+const BASE_URL: &str = "https://api.congress.gov/v3";
+/// The largest page the API serves, used when following every page of a list.
+const MAX_PAGE_SIZE: u32 = 250;
+const MAX_RETRIES: u32 = 5;
+/// A request that takes longer is given up and retried like a failed connection.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum CongressError {
+    #[error("Request to Congress.gov failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Congress.gov returned {status} for {url}")]
+    Status { status: StatusCode, url: String },
+    #[error("Could not decode Congress.gov response from {url}: {source}")]
+    Decode {
+        url: String,
+        source: serde_json::Error,
+    },
+    #[error("Congress.gov still failing for {url} after {attempts} attempts")]
+    RetriesExhausted { url: String, attempts: u32 },
+}
+
+/// One page of a list endpoint along with the total the API reports.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct Pagination {
+    count: u32,
+    next: Option<String>,
+}
+
+/// Filters for the `/bill` list endpoints.
+#[derive(Debug, Clone)]
+pub struct BillQuery {
+    pub congress: Option<u32>,
+    pub bill_type: Option<String>,
+    pub offset: u32,
+    pub limit: u32,
+    pub from_date_time: Option<String>,
+    pub to_date_time: Option<String>,
+    pub sort: Option<String>,
+}
+
+impl Default for BillQuery {
+    fn default() -> Self {
+        Self {
+            congress: None,
+            bill_type: None,
+            offset: 0,
+            limit: 20,
+            from_date_time: None,
+            to_date_time: None,
+            sort: None,
+        }
+    }
+}
+
+impl BillQuery {
+    fn path(&self) -> String {
+        match (self.congress, &self.bill_type) {
+            (Some(congress), Some(bill_type)) => {
+                format!("/bill/{congress}/{}", bill_type.to_lowercase())
+            }
+            (Some(congress), None) => format!("/bill/{congress}"),
+            _ => "/bill".to_string(),
+        }
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("offset", self.offset.to_string()),
+            ("limit", self.limit.to_string()),
+        ];
+        if let Some(from) = &self.from_date_time {
+            params.push(("fromDateTime", from.clone()));
+        }
+        if let Some(to) = &self.to_date_time {
+            params.push(("toDateTime", to.clone()));
+        }
+        if let Some(sort) = &self.sort {
+            params.push(("sort", sort.clone()));
+        }
+        params
+    }
+}
+
+/// Cheap to clone, every clone shares the HTTP connection pool and the rate limiter.
+#[derive(Clone)]
+pub struct CongressClient {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    http: reqwest::Client,
+    api_key: String,
+    limiter: RateLimiter,
+}
+
+impl fmt::Debug for CongressClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CongressClient")
+            .field("limiter", &self.inner.limiter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Gets the client provided to server functions, like [`crate::db::db`] does for the database.
+///
+/// The client is only provided when an API key is configured, otherwise this fails with [`API_KEY_MISSING`].
+pub fn client() -> Result<CongressClient, ServerFnError> {
+    use_context::<CongressClient>()
+        .ok_or_else(|| ServerFnError::ServerError(API_KEY_MISSING.into()))
+}
+
+impl CongressClient {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                http: reqwest::Client::builder()
+                    .timeout(REQUEST_TIMEOUT)
+                    .build()
+                    .expect("the Congress.gov client only sets a timeout"),
+                api_key: api_key.into(),
+                limiter: RateLimiter::congress_gov(),
+            }),
+        }
+    }
+
+    /// One page of bills, most recently updated first unless `query.sort` says otherwise.
+    pub async fn bills(&self, query: &BillQuery) -> Result<Page<BillListItem>, CongressError> {
+        self.list(&query.path(), "bills", query.params(), false)
+            .await
+    }
+
+    /// Every bill matching `query`, following `pagination.next` from `query.offset` on.
+    pub async fn all_bills(&self, query: &BillQuery) -> Result<Vec<BillListItem>, CongressError> {
+        let query = BillQuery {
+            limit: MAX_PAGE_SIZE,
+            ..query.clone()
+        };
+        Ok(self
+            .list(&query.path(), "bills", query.params(), true)
+            .await?
+            .items)
+    }
+
+    pub async fn bill(&self, id: &BillId) -> Result<BillDetail, CongressError> {
+        #[derive(Deserialize)]
+        struct Response {
+            bill: BillDetail,
+        }
+
+        let url = format!("{BASE_URL}{}", bill_path(id));
+        let res: Response = self.get(&url, &[("format", "json".to_string())]).await?;
+        Ok(res.bill)
+    }
+
+    pub async fn bill_actions(&self, id: &BillId) -> Result<Vec<BillAction>, CongressError> {
+        let path = format!("{}/actions", bill_path(id));
+        self.list_all(&path, "actions").await
+    }
+
+    pub async fn bill_cosponsors(&self, id: &BillId) -> Result<Vec<Cosponsor>, CongressError> {
+        let path = format!("{}/cosponsors", bill_path(id));
+        self.list_all(&path, "cosponsors").await
+    }
+
+    pub async fn bill_committees(&self, id: &BillId) -> Result<Vec<BillCommittee>, CongressError> {
+        let path = format!("{}/committees", bill_path(id));
+        self.list_all(&path, "committees").await
+    }
+
+    /// Legislative subjects and policy area. Bills rarely have more than one page of subjects, so only the first is read.
+    pub async fn bill_subjects(&self, id: &BillId) -> Result<BillSubjects, CongressError> {
+        #[derive(Deserialize)]
+        struct Response {
+            subjects: BillSubjects,
+        }
+
+        let url = format!("{BASE_URL}{}/subjects", bill_path(id));
+        let params = [
+            ("format", "json".to_string()),
+            ("limit", MAX_PAGE_SIZE.to_string()),
+        ];
+        let res: Response = self.get(&url, &params).await?;
+        Ok(res.subjects)
+    }
+
+    pub async fn bill_related_bills(&self, id: &BillId) -> Result<Vec<RelatedBill>, CongressError> {
+        let path = format!("{}/relatedbills", bill_path(id));
+        self.list_all(&path, "relatedBills").await
+    }
+
+    pub async fn bill_summaries(&self, id: &BillId) -> Result<Vec<BillSummary>, CongressError> {
+        let path = format!("{}/summaries", bill_path(id));
+        self.list_all(&path, "summaries").await
+    }
+
+    pub async fn bill_text(&self, id: &BillId) -> Result<Vec<TextVersion>, CongressError> {
+        let path = format!("{}/text", bill_path(id));
+        self.list_all(&path, "textVersions").await
+    }
+
+    pub async fn members(&self, offset: u32, limit: u32) -> Result<Page<Member>, CongressError> {
+        let params = vec![("offset", offset.to_string()), ("limit", limit.to_string())];
+        self.list("/member", "members", params, false).await
+    }
+
+    /// Every member of the current Congress.
+    pub async fn current_members(&self) -> Result<Vec<Member>, CongressError> {
+        let params = vec![
+            ("currentMember", "true".to_string()),
+            ("limit", MAX_PAGE_SIZE.to_string()),
+        ];
+        Ok(self.list("/member", "members", params, true).await?.items)
+    }
+
+    pub async fn member(&self, bioguide_id: &str) -> Result<MemberDetail, CongressError> {
+        #[derive(Deserialize)]
+        struct Response {
+            member: MemberDetail,
+        }
+
+        let url = format!("{BASE_URL}/member/{bioguide_id}");
+        let res: Response = self.get(&url, &[("format", "json".to_string())]).await?;
+        Ok(res.member)
+    }
+
+    /// The most recent bills and amendments a member sponsored, up to `limit`, with the total count.
+    pub async fn member_sponsored_legislation(
+        &self,
+        bioguide_id: &str,
+        limit: u32,
+    ) -> Result<Page<MemberLegislation>, CongressError> {
+        let path = format!("/member/{bioguide_id}/sponsored-legislation");
+        let params = vec![("limit", limit.to_string())];
+        self.list(&path, "sponsoredLegislation", params, false)
+            .await
+    }
+
+    /// The most recent bills and amendments a member cosponsored, up to `limit`, with the total count.
+    pub async fn member_cosponsored_legislation(
+        &self,
+        bioguide_id: &str,
+        limit: u32,
+    ) -> Result<Page<MemberLegislation>, CongressError> {
+        let path = format!("/member/{bioguide_id}/cosponsored-legislation");
+        let params = vec![("limit", limit.to_string())];
+        self.list(&path, "cosponsoredLegislation", params, false)
+            .await
+    }
+
+    /// Every committee, optionally limited to one chamber (`house`, `senate` or `joint`).
+    pub async fn committees(&self, chamber: Option<&str>) -> Result<Vec<Committee>, CongressError> {
+        let path = match chamber {
+            Some(chamber) => format!("/committee/{}", chamber.to_lowercase()),
+            None => "/committee".to_string(),
+        };
+        self.list_all(&path, "committees").await
+    }
+
+    async fn list_all<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        key: &str,
+    ) -> Result<Vec<T>, CongressError> {
+        let params = vec![("limit", MAX_PAGE_SIZE.to_string())];
+        Ok(self.list(path, key, params, true).await?.items)
+    }
+
+    /// Reads the array under `key` of a list endpoint, following `pagination.next` when `follow` is set.
+    async fn list<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        key: &str,
+        mut params: Vec<(&'static str, String)>,
+        follow: bool,
+    ) -> Result<Page<T>, CongressError> {
+        let mut url = format!("{BASE_URL}{path}");
+        params.push(("format", "json".to_string()));
+
+        let mut items = Vec::new();
+        let mut count = None;
+        loop {
+            let mut body: Value = self.get(&url, &params).await?;
+            let page: Vec<T> = match body.get_mut(key) {
+                Some(value) => serde_json::from_value(value.take()).map_err(|source| {
+                    CongressError::Decode {
+                        url: url.clone(),
+                        source,
+                    }
+                })?,
+                None => Vec::new(),
+            };
+            items.extend(page);
+
+            let pagination = body
+                .get_mut("pagination")
+                .and_then(|value| serde_json::from_value::<Pagination>(value.take()).ok());
+            let next = pagination.and_then(|pagination| {
+                count = Some(pagination.count);
+                pagination.next
+            });
+            match next {
+                // `next` already carries offset, limit and format.
+                Some(next) if follow => {
+                    url = next;
+                    params.clear();
+                }
+                _ => break,
+            }
+        }
+
+        let count = count.unwrap_or(items.len() as u32);
+        Ok(Page { items, count })
+    }
+
+    /// Downloads a bill text file (HTML or XML). These are static files on congress.gov,
+    /// so the API key is not sent and the API quota is not used.
+    pub async fn text_document(&self, url: &str) -> Result<String, CongressError> {
+        let res = self.send(url, &[], false).await?;
+        Ok(res.text().await?)
+    }
+
+    /// Sends a rate limited API GET and decodes the JSON body.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &[(&str, String)],
+    ) -> Result<T, CongressError> {
+        let res = self.send(url, params, true).await?;
+        let bytes = res.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(|source| CongressError::Decode {
+            url: url.to_string(),
+            source,
+        })
+    }
+
+    /// Sends a GET, retrying 429 and 5xx responses with exponential backoff.
+    async fn send(
+        &self,
+        url: &str,
+        params: &[(&str, String)],
+        api: bool,
+    ) -> Result<reqwest::Response, CongressError> {
+        let mut attempt = 0;
+        loop {
+            let mut request = self.inner.http.get(url).query(params);
+            if api {
+                self.inner.limiter.acquire().await;
+                request = request.header("X-Api-Key", &self.inner.api_key);
+            }
+
+            let retry_after = match request.send().await {
+                Ok(res) if res.status().is_success() => return Ok(res),
+                Ok(res) if is_retryable(res.status()) => retry_after(&res),
+                Ok(res) => {
+                    return Err(CongressError::Status {
+                        status: res.status(),
+                        url: url.to_string(),
+                    })
+                }
+                Err(err) if err.is_timeout() || err.is_connect() => None,
+                Err(err) => return Err(err.into()),
+            };
+
+            attempt += 1;
+            if attempt > MAX_RETRIES {
+                return Err(CongressError::RetriesExhausted {
+                    url: url.to_string(),
+                    attempts: attempt,
+                });
+            }
+            tokio::time::sleep(retry_after.unwrap_or_else(|| backoff(attempt))).await;
+        }
+    }
+}
+
+/// Built from the parsed id, so only real bill types and numbers ever reach the API path.
+fn bill_path(id: &BillId) -> String {
+    format!(
+        "/bill/{}/{}/{}",
+        id.congress,
+        id.bill_type.slug(),
+        id.number
+    )
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn retry_after(res: &reqwest::Response) -> Option<Duration> {
+    res.headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// 1s, 2s, 4s, ... capped at a minute.
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt.saturating_sub(1)).min(60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bill_query_path() {
+        let query = BillQuery {
+            congress: Some(118),
+            bill_type: Some("HR".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query.path(), "/bill/118/hr");
+        assert_eq!(BillQuery::default().path(), "/bill");
+
+        let id: BillId = "S.J.Res. 12 (118th)".parse().unwrap();
+        assert_eq!(bill_path(&id), "/bill/118/sjres/12");
+    }
+
+    #[test]
+    fn test_bill_query_sort_param() {
+        use crate::domain::bills::BillSort;
+
+        let query = BillQuery {
+            sort: Some(BillSort::Newest.api().into()),
+            ..Default::default()
+        };
+        let request = reqwest::Client::new()
+            .get(format!("{BASE_URL}{}", query.path()))
+            .query(&query.params())
+            .build()
+            .unwrap();
+        let params = request.url().query().unwrap();
+        assert!(params.contains("sort=updateDate+desc"));
+        assert!(!params.contains("%2B"));
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(4));
+        assert_eq!(backoff(20), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket shared by every request of a [`CongressClient`](super::CongressClient).
+///
+/// Congress.gov allows 5,000 requests per hour per key, the bucket starts full and refills continuously.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    per_second: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(requests: u32, per: Duration) -> Self {
+        let capacity = f64::from(requests);
+        Self {
+            capacity,
+            per_second: capacity / per.as_secs_f64(),
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// The Congress.gov quota of 5,000 requests an hour.
+    pub fn congress_gov() -> Self {
+        Self::new(5_000, Duration::from_secs(60 * 60))
+    }
+
+    /// Waits until a token is available and takes it.
+    pub async fn acquire(&self) {
+        loop {
+            match self.try_acquire_at(Instant::now()) {
+                Ok(()) => return,
+                Err(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    /// Takes a token, or returns how long until one is available.
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * self.per_second).min(self.capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.per_second,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_empties_and_refills() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(2));
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_ok());
+        let wait = limiter.try_acquire_at(start).unwrap_err();
+        assert!(wait <= Duration::from_secs(1));
+
+        assert!(limiter
+            .try_acquire_at(start + Duration::from_secs(1))
+            .is_ok());
+    }
+}
@@ -1,8 +1,107 @@
 use leptos::*;
-use surrealdb::engine::remote::ws::Client;
-use surrealdb::Surreal;
+use surrealdb::engine::any::{self, Any};
+use surrealdb::opt::auth::{Database, Namespace, Root, Scope};
+use surrealdb::{Connection, Surreal};
 
-pub fn db() -> Result<Surreal<Client>, ServerFnError> {
-    use_context::<Surreal<Client>>()
-        .ok_or_else(|| ServerFnError::ServerError("Pool missing.".into()))
+use crate::config::{AuthMode, DatabaseSettings};
+
+/// The engine the app runs on. `engine::any` picks the remote or embedded engine from the endpoint scheme,
+/// embedded engines are enabled with the `kv-mem` and `kv-rocksdb` cargo features.
+pub type Engine = Any;
+
+pub fn db() -> Result<Surreal<Engine>, ServerFnError> {
+    db_for::<Engine>()
+}
+
+/// Same as [`db`] for a handle provided with a concrete engine type.
+pub fn db_for<C: Connection>() -> Result<Surreal<C>, ServerFnError> {
+    use_context::<Surreal<C>>().ok_or_else(|| ServerFnError::ServerError("Pool missing.".into()))
+}
+
+/// Connects to SurrealDB, signs in with the configured auth mode and selects the namespace / database.
+pub async fn connect(settings: &DatabaseSettings) -> surrealdb::Result<Surreal<Engine>> {
+    let db = any::connect(settings.address()).await?;
+    signin(&db, settings).await?;
+
+    db.use_ns(&settings.namespace)
+        .use_db(&settings.database)
+        .await?;
+
+    Ok(db)
+}
+
+async fn signin<C: Connection>(
+    db: &Surreal<C>,
+    settings: &DatabaseSettings,
+) -> surrealdb::Result<()> {
+    // Embedded engines have no users to sign in as.
+    if settings.is_embedded() {
+        return Ok(());
+    }
+
+    let username = settings.username();
+    let password = settings.password();
+    match settings.auth {
+        AuthMode::Root => {
+            db.signin(Root { username, password }).await?;
+        }
+        AuthMode::Namespace => {
+            db.signin(Namespace {
+                namespace: &settings.namespace,
+                username,
+                password,
+            })
+            .await?;
+        }
+        AuthMode::Database => {
+            db.signin(Database {
+                namespace: &settings.namespace,
+                database: &settings.database,
+                username,
+                password,
+            })
+            .await?;
+        }
+        AuthMode::Scope => {
+            db.signin(Scope {
+                namespace: &settings.namespace,
+                database: &settings.database,
+                scope: settings.scope.as_deref().unwrap_or_default(),
+                params: serde_json::json!({ "username": username, "password": password }),
+            })
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Person {
+        first: String,
+    }
+
+    #[tokio::test]
+    async fn test_connect_in_memory() {
+        let settings = DatabaseSettings {
+            endpoint: "mem://".to_string(),
+            ..Default::default()
+        };
+        let db = connect(&settings).await.unwrap();
+
+        let _: Vec<Person> = db
+            .create("person")
+            .content(Person {
+                first: "Ada".to_string(),
+            })
+            .await
+            .unwrap();
+        let people: Vec<Person> = db.select("person").await.unwrap();
+
+        assert_eq!(people.len(), 1);
+    }
 }
@@ -0,0 +1,8 @@
+use leptos::ServerFnError;
+
+/// Error message of server functions that need Congress.gov when no API key is configured.
+pub const API_KEY_MISSING: &str = "API key not configured";
+
+pub fn is_api_key_missing(error: &ServerFnError) -> bool {
+    matches!(error, ServerFnError::ServerError(message) if message == API_KEY_MISSING)
+}
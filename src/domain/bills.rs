@@ -1,9 +1,42 @@
+use std::convert::Infallible;
 use std::fmt::Display;
+use std::str::FromStr;
 
+use chrono::{Datelike, NaiveDate, Utc};
 use leptos::IntoView;
-use serde::{Deserialize, Serialize};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use surrealdb::sql::{Id, Thing};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// The Congress in session on `date`. Each Congress starts on January 3rd of an odd year.
+pub fn congress_on(date: NaiveDate) -> u32 {
+    let year = if date.year() % 2 == 1 && date.ordinal() < 3 {
+        date.year() - 1
+    } else {
+        date.year()
+    };
+    ((year - 1789) / 2 + 1) as u32
+}
+
+/// The Congress in session today, used when a bill reference doesn't name one.
+pub fn current_congress() -> u32 {
+    congress_on(Utc::now().date_naive())
+}
+
+/// "118th", "121st", "122nd", ...
+pub fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BillType {
     HR,
     S,
@@ -14,6 +47,8 @@ pub enum BillType {
     HRES,
     SRES,
 }
+
+/// The Congress.gov `type` code, e.g. `HR`.
 impl Display for BillType {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         use BillType::*;
@@ -36,7 +71,707 @@ impl IntoView for BillType {
     }
 }
 
+impl BillType {
+    pub const ALL: [BillType; 8] = {
+        use BillType::*;
+        [HR, S, HJRES, SJRES, HCONRES, SCONRES, HRES, SRES]
+    };
+
+    /// The abbreviation used when citing a bill, e.g. `H.J.Res.`.
+    pub fn citation(&self) -> &'static str {
+        use BillType::*;
+        match self {
+            HR => "H.R.",
+            S => "S.",
+            HJRES => "H.J.Res.",
+            SJRES => "S.J.Res.",
+            HCONRES => "H.Con.Res.",
+            SCONRES => "S.Con.Res.",
+            HRES => "H.Res.",
+            SRES => "S.Res.",
+        }
+    }
+
+    /// The official name, e.g. `House Joint Resolution`.
+    pub fn long_name(&self) -> &'static str {
+        use BillType::*;
+        match self {
+            HR => "House Bill",
+            S => "Senate Bill",
+            HJRES => "House Joint Resolution",
+            SJRES => "Senate Joint Resolution",
+            HCONRES => "House Concurrent Resolution",
+            SCONRES => "Senate Concurrent Resolution",
+            HRES => "House Simple Resolution",
+            SRES => "Senate Simple Resolution",
+        }
+    }
+
+    /// The lowercase code Congress.gov uses in URLs, e.g. `hjres`.
+    pub fn slug(&self) -> &'static str {
+        use BillType::*;
+        match self {
+            HR => "hr",
+            S => "s",
+            HJRES => "hjres",
+            SJRES => "sjres",
+            HCONRES => "hconres",
+            SCONRES => "sconres",
+            HRES => "hres",
+            SRES => "sres",
+        }
+    }
+
+    /// The chamber the bill was introduced in.
+    pub fn chamber(&self) -> Chamber {
+        use BillType::*;
+        match self {
+            HR | HJRES | HCONRES | HRES => Chamber::House,
+            S | SJRES | SCONRES | SRES => Chamber::Senate,
+        }
+    }
+
+    pub fn is_resolution(&self) -> bool {
+        !matches!(self, BillType::HR | BillType::S)
+    }
+
+    /// Joint resolutions go to the President like bills do.
+    pub fn is_joint(&self) -> bool {
+        matches!(self, BillType::HJRES | BillType::SJRES)
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("'{0}' is not a bill type")]
+pub struct BillTypeError(pub String);
+
+/// Accepts the code, citation or long name ignoring case, dots and spaces,
+/// e.g. `hconres`, `H. Res.` or `S.Con.Res.`.
+impl FromStr for BillType {
+    type Err = BillTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalize = |s: &str| {
+            s.chars()
+                .filter(|c| *c != '.' && !c.is_whitespace())
+                .collect::<String>()
+                .to_uppercase()
+        };
+        let code = normalize(s);
+        Self::ALL
+            .into_iter()
+            .find(|bill_type| {
+                bill_type.to_string() == code || normalize(bill_type.long_name()) == code
+            })
+            .ok_or_else(|| BillTypeError(s.trim().to_string()))
+    }
+}
+
+impl Serialize for BillType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BillType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// `H.R. 7261`, `hr7261-118`, `118-hr-7261` or `S.J.Res. 12 (118th)`.
+static BILL_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?<congress_prefix>\d+)-)?(?<bill_type>[A-Za-z][A-Za-z.\s]*?)[\s-]*(?<number>\d+)\s*(?:-(?<congress_suffix>\d+)|\((?<congress_paren>\d+)(?:st|nd|rd|th)?(?:\s+Congress)?\))?\s*$",
+    )
+    .unwrap()
+});
+
+/// Identifies a bill across routes, feeds and the database.
+///
+/// Citations without a Congress, e.g. `H.R. 7261`, are taken to be in the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BillId {
+    pub congress: u32,
+    pub bill_type: BillType,
+    pub number: u32,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum BillIdError {
+    #[error("'{0}' is not a bill citation")]
+    Format(String),
+    #[error("'{found}' is not a bill type in '{input}'")]
+    BillType { found: String, input: String },
+    #[error("'{0}' is not a bill record id")]
+    Record(String),
+}
+
+impl BillId {
+    /// The SurrealDB table bills are stored in.
+    pub const TABLE: &'static str = "bill";
+
+    pub fn new(congress: u32, bill_type: BillType, number: u32) -> Self {
+        Self {
+            congress,
+            bill_type,
+            number,
+        }
+    }
+
+    /// `118-hr-7261`, used as the record key and in serialized form.
+    pub fn key(&self) -> String {
+        format!(
+            "{}-{}-{}",
+            self.congress,
+            self.bill_type.slug(),
+            self.number
+        )
+    }
+
+    /// The bill page, e.g. `/bills/118/hr/7261`.
+    pub fn url(&self) -> String {
+        format!(
+            "/bills/{}/{}/{}",
+            self.congress,
+            self.bill_type.slug(),
+            self.number
+        )
+    }
+
+    /// `bill:⟨118-hr-7261⟩`
+    pub fn thing(&self) -> Thing {
+        Thing::from((Self::TABLE, self.key().as_str()))
+    }
+}
+
+impl Display for BillId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} {} ({})",
+            self.bill_type.citation(),
+            self.number,
+            ordinal(self.congress)
+        )
+    }
+}
+
+impl FromStr for BillId {
+    type Err = BillIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format_error = || BillIdError::Format(s.to_string());
+        let caps = BILL_ID.captures(s).ok_or_else(format_error)?;
+
+        let bill_type_match = &caps["bill_type"];
+        let bill_type = bill_type_match
+            .parse::<BillType>()
+            .map_err(|_| BillIdError::BillType {
+                found: bill_type_match.trim().to_string(),
+                input: s.to_string(),
+            })?;
+        let number = caps["number"].parse().map_err(|_| format_error())?;
+        let congress = match caps
+            .name("congress_prefix")
+            .or_else(|| caps.name("congress_suffix"))
+            .or_else(|| caps.name("congress_paren"))
+        {
+            Some(congress) => congress.as_str().parse().map_err(|_| format_error())?,
+            None => current_congress(),
+        };
+
+        Ok(Self::new(congress, bill_type, number))
+    }
+}
+
+impl Serialize for BillId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.key())
+    }
+}
+
+impl<'de> Deserialize<'de> for BillId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        key.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<BillId> for Thing {
+    fn from(id: BillId) -> Self {
+        id.thing()
+    }
+}
+
+impl TryFrom<&Thing> for BillId {
+    type Error = BillIdError;
+
+    fn try_from(thing: &Thing) -> Result<Self, Self::Error> {
+        match &thing.id {
+            Id::String(key) if thing.tb == Self::TABLE => key.parse(),
+            _ => Err(BillIdError::Record(thing.to_string())),
+        }
+    }
+}
+
+impl TryFrom<Thing> for BillId {
+    type Error = BillIdError;
+
+    fn try_from(thing: Thing) -> Result<Self, Self::Error> {
+        Self::try_from(&thing)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LatestAction {
+    pub action_date: String,
+    pub text: String,
+}
+
+/// A bill as listed by the Congress.gov `/bill` endpoints.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BillListItem {
+    pub congress: u32,
+    pub number: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub bill_type: BillType,
+    pub origin_chamber: Option<String>,
+    pub update_date: Option<String>,
+    pub latest_action: Option<LatestAction>,
+}
+
+impl BillListItem {
+    /// `None` when the API sends a number that isn't one.
+    pub fn id(&self) -> Option<BillId> {
+        let number = self.number.parse().ok()?;
+        Some(BillId::new(self.congress, self.bill_type, number))
+    }
+}
+
+impl From<&BillDetail> for BillListItem {
+    fn from(detail: &BillDetail) -> Self {
+        Self {
+            congress: detail.congress,
+            number: detail.number.clone(),
+            title: detail.title.clone(),
+            bill_type: detail.bill_type,
+            origin_chamber: detail.origin_chamber.map(|chamber| chamber.to_string()),
+            update_date: detail.update_date.clone(),
+            latest_action: detail.latest_action.clone(),
+        }
+    }
+}
+
+/// Order of the bill list. Congress.gov only sorts by update date.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BillSort {
+    #[default]
+    Newest,
+    Oldest,
+}
+
+impl BillSort {
+    pub const ALL: [BillSort; 2] = [BillSort::Newest, BillSort::Oldest];
+
+    /// Value of the `sort` query parameter, a leading `-` sorts descending.
+    pub fn param(&self) -> &'static str {
+        match self {
+            BillSort::Newest => "-updateDate",
+            BillSort::Oldest => "updateDate",
+        }
+    }
+
+    pub fn from_param(param: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|sort| sort.param() == param)
+    }
+
+    /// Value of the Congress.gov `sort` parameter, before URL encoding turns the space into `+`.
+    pub fn api(&self) -> &'static str {
+        match self {
+            BillSort::Newest => "updateDate desc",
+            BillSort::Oldest => "updateDate asc",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BillSort::Newest => "Recently updated",
+            BillSort::Oldest => "Least recently updated",
+        }
+    }
+}
+
+/// Filters and page of the bill list, carried in the `/bills` query string,
+/// e.g. `?congress=118&type=hr&page=3&sort=-updateDate`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BillListQuery {
+    pub congress: Option<u32>,
+    pub bill_type: Option<BillType>,
+    /// Starts at 1.
+    pub page: u32,
+    pub sort: BillSort,
+}
+
+impl Default for BillListQuery {
+    fn default() -> Self {
+        Self {
+            congress: None,
+            bill_type: None,
+            page: 1,
+            sort: BillSort::default(),
+        }
+    }
+}
+
+impl BillListQuery {
+    pub const PAGE_SIZE: u32 = 20;
+
+    /// Reads the query parameters returned by `get`, ignoring values that don't parse.
+    pub fn from_params<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let get = |key| get(key).map(str::trim).filter(|value| !value.is_empty());
+        Self {
+            congress: get("congress").and_then(|congress| congress.parse().ok()),
+            bill_type: get("type").and_then(|bill_type| bill_type.parse().ok()),
+            page: get("page")
+                .and_then(|page| page.parse().ok())
+                .unwrap_or(1)
+                .max(1),
+            sort: get("sort")
+                .and_then(BillSort::from_param)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The query string, starting with `?` unless every value is the default.
+    pub fn to_query_string(&self) -> String {
+        let mut params = Vec::new();
+        if let Some(congress) = self.congress {
+            params.push(format!("congress={congress}"));
+        }
+        if let Some(bill_type) = self.bill_type {
+            params.push(format!("type={}", bill_type.slug()));
+        }
+        if self.page > 1 {
+            params.push(format!("page={}", self.page));
+        }
+        if self.sort != BillSort::default() {
+            params.push(format!("sort={}", self.sort.param()));
+        }
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", params.join("&"))
+        }
+    }
+
+    pub fn with_page(&self, page: u32) -> Self {
+        Self {
+            page,
+            ..self.clone()
+        }
+    }
+
+    /// Bills to skip before this page. Pages past the end of `u32` stay on the last offset.
+    pub fn offset(&self) -> u32 {
+        (self.page.max(1) - 1).saturating_mul(Self::PAGE_SIZE)
+    }
+
+    /// Pages needed for `total` bills, at least one.
+    pub fn pages(total: u32) -> u32 {
+        total.div_ceil(Self::PAGE_SIZE).max(1)
+    }
+}
+
+/// A chamber of Congress, e.g. the one a bill originated in.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chamber {
+    House,
+    Senate,
+    /// Not specific to one chamber, e.g. an action by the President or the Library of Congress.
+    Both,
+}
+
+impl Display for Chamber {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Chamber::House => write!(f, "House"),
+            Chamber::Senate => write!(f, "Senate"),
+            Chamber::Both => write!(f, "Both"),
+        }
+    }
+}
+
+/// Count and API url of a sub-resource that is fetched separately, e.g. a bill's cosponsors.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResourceRef {
+    pub count: u32,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PolicyArea {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Sponsor {
+    pub bioguide_id: String,
+    pub full_name: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub party: String,
+    pub state: String,
+    pub district: Option<u32>,
+}
+
+/// A Congressional Budget Office cost estimate.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CboCostEstimate {
+    pub title: String,
+    pub description: Option<String>,
+    pub pub_date: Option<String>,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Law {
+    pub number: String,
+    #[serde(rename = "type")]
+    pub law_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommitteeReport {
+    pub citation: String,
+    pub url: Option<String>,
+}
+
+/// A bill from the Congress.gov `/bill/{congress}/{type}/{number}` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BillDetail {
+    pub congress: u32,
+    pub number: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub bill_type: BillType,
+    pub introduced_date: Option<String>,
+    pub origin_chamber: Option<Chamber>,
+    pub update_date: Option<String>,
+    pub latest_action: Option<LatestAction>,
+    pub policy_area: Option<PolicyArea>,
+    #[serde(default)]
+    pub sponsors: Vec<Sponsor>,
+    pub cosponsors: Option<ResourceRef>,
+    pub committees: Option<ResourceRef>,
+    pub subjects: Option<ResourceRef>,
+    pub related_bills: Option<ResourceRef>,
+    pub actions: Option<ResourceRef>,
+    pub text_versions: Option<ResourceRef>,
+    #[serde(default)]
+    pub cbo_cost_estimates: Vec<CboCostEstimate>,
+    #[serde(default)]
+    pub committee_reports: Vec<CommitteeReport>,
+    #[serde(default)]
+    pub laws: Vec<Law>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommitteeActivity {
+    pub name: String,
+    pub date: Option<String>,
+}
+
+/// A committee a bill was referred to, from the `/committees` sub-resource.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BillCommittee {
+    pub name: String,
+    pub system_code: String,
+    pub chamber: Option<String>,
+    #[serde(rename = "type")]
+    pub committee_type: Option<String>,
+    #[serde(default)]
+    pub activities: Vec<CommitteeActivity>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LegislativeSubject {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BillSubjects {
+    #[serde(default)]
+    pub legislative_subjects: Vec<LegislativeSubject>,
+    pub policy_area: Option<PolicyArea>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationshipDetail {
+    pub identified_by: String,
+    #[serde(rename = "type")]
+    pub relationship_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedBill {
+    pub congress: u32,
+    /// The API sends a number here, unlike the string in bill lists.
+    pub number: u32,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub bill_type: BillType,
+    pub latest_action: Option<LatestAction>,
+    #[serde(default)]
+    pub relationship_details: Vec<RelationshipDetail>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceSystem {
+    pub code: Option<u32>,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedVote {
+    pub chamber: String,
+    pub congress: u32,
+    pub date: String,
+    pub roll_number: u32,
+    pub session_number: u32,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BillAction {
+    pub action_date: String,
+    pub action_time: Option<String>,
+    pub text: String,
+    #[serde(rename = "type")]
+    pub action_type: Option<String>,
+    pub action_code: Option<String>,
+    pub source_system: Option<SourceSystem>,
+    #[serde(default)]
+    pub recorded_votes: Vec<RecordedVote>,
+}
+
+impl BillAction {
+    /// The chamber that took the action, from its source system.
+    ///
+    /// Congress.gov codes the Senate as 0, House committee and floor actions as 1 and 2,
+    /// and actions recorded by the Library of Congress (9) apply to the bill as a whole.
+    pub fn chamber(&self) -> Chamber {
+        match self.source_system.as_ref().and_then(|system| system.code) {
+            Some(0) => Chamber::Senate,
+            Some(1) | Some(2) => Chamber::House,
+            _ => Chamber::Both,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Cosponsor {
+    pub bioguide_id: String,
+    pub full_name: String,
+    pub party: String,
+    pub state: String,
+    pub district: Option<u32>,
+    pub sponsorship_date: String,
+    pub sponsorship_withdrawn_date: Option<String>,
+    #[serde(default)]
+    pub is_original_cosponsor: bool,
+}
+
+/// A CRS summary of one version of a bill.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BillSummary {
+    pub action_date: String,
+    pub action_desc: String,
+    pub text: String,
+    pub update_date: Option<String>,
+    pub version_code: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TextFormat {
+    #[serde(rename = "type")]
+    pub format_type: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TextVersion {
+    pub date: Option<String>,
+    #[serde(rename = "type")]
+    pub version_type: String,
+    #[serde(default)]
+    pub formats: Vec<TextFormat>,
+}
+
+/// Matches the version code at the end of a GPO file name, e.g. `ih` in `BILLS-118hr7261ih.htm`.
+static TEXT_FILE_VERSION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"BILLS-\d+[a-z]+\d+(?<version>[a-z]+)\.\w+$").unwrap());
+
+impl TextVersion {
+    /// The API only labels versions ("Introduced in House"), the code comes from the file names.
+    pub fn abbreviation(&self) -> Option<BillTextVersionAbbreviation> {
+        self.formats.iter().find_map(|format| {
+            let caps = TEXT_FILE_VERSION.captures(&format.url)?;
+            BillTextVersionAbbreviation::from_code(&caps["version"])
+        })
+    }
+
+    fn format_url(&self, format_type: &str) -> Option<&str> {
+        self.formats
+            .iter()
+            .find(|format| format.format_type == format_type)
+            .map(|format| format.url.as_str())
+    }
+
+    pub fn html_url(&self) -> Option<&str> {
+        self.format_url("Formatted Text")
+    }
+
+    pub fn xml_url(&self) -> Option<&str> {
+        self.format_url("Formatted XML")
+    }
+
+    pub fn pdf_url(&self) -> Option<&str> {
+        self.format_url("PDF")
+    }
+
+    /// Orders versions the way a bill moves through Congress: by the stage their
+    /// [`BillTextVersionAbbreviation`] marks, then by date. Versions without a known code go last.
+    pub fn sort(versions: &mut [TextVersion]) {
+        versions.sort_by_cached_key(|version| {
+            let stage = version
+                .abbreviation()
+                .and_then(|abbreviation| abbreviation.stage());
+            (stage.is_none(), stage, version.date.clone())
+        });
+    }
+}
+
+/// GPO bill text version codes. Serializes as the code, e.g. `"IH"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BillTextVersionAbbreviation {
     AS,
     ASH,
@@ -79,6 +814,8 @@ pub enum BillTextVersionAbbreviation {
     SC,
     STATPVT,
     STAT,
+    /// A code GPO added after this list was written, kept uppercased.
+    Unknown(String),
 }
 
 impl Display for BillTextVersionAbbreviation {
@@ -126,6 +863,525 @@ impl Display for BillTextVersionAbbreviation {
             SC => write!(f, "Sponsor Change"),
             STATPVT => write!(f, "Statutes at Large (Private Law)"),
             STAT => write!(f, "Statute"),
+            Unknown(code) => write!(f, "Unknown Version ({code})"),
+        }
+    }
+}
+
+impl BillTextVersionAbbreviation {
+    pub const ALL: [BillTextVersionAbbreviation; 41] = {
+        use BillTextVersionAbbreviation::*;
+        [
+            AS, ASH, ATH, ATS, CDH, CDS, CPH, CPS, EAH, EAS, EH, ENR, ES, FPH, FPS, HDS, IH, IPH,
+            IPS, IS, LTH, LTS, PAP, PCS, PP, PVTL, PL, RCH, RCS, RDS, RFH, RFS, RH, RHUC, RIH, RS,
+            RTH, RTS, SC, STATPVT, STAT,
+        ]
+    };
+
+    /// The GPO code, e.g. `IH`. URLs use it lowercased.
+    pub fn code(&self) -> &str {
+        use BillTextVersionAbbreviation::*;
+        match self {
+            AS => "AS",
+            ASH => "ASH",
+            ATH => "ATH",
+            ATS => "ATS",
+            CDH => "CDH",
+            CDS => "CDS",
+            CPH => "CPH",
+            CPS => "CPS",
+            EAH => "EAH",
+            EAS => "EAS",
+            EH => "EH",
+            ENR => "ENR",
+            ES => "ES",
+            FPH => "FPH",
+            FPS => "FPS",
+            HDS => "HDS",
+            IH => "IH",
+            IPH => "IPH",
+            IPS => "IPS",
+            IS => "IS",
+            LTH => "LTH",
+            LTS => "LTS",
+            PAP => "PAP",
+            PCS => "PCS",
+            PP => "PP",
+            PVTL => "PVTL",
+            PL => "PL",
+            RCH => "RCH",
+            RCS => "RCS",
+            RDS => "RDS",
+            RFH => "RFH",
+            RFS => "RFS",
+            RH => "RH",
+            RHUC => "RHUC",
+            RIH => "RIH",
+            RS => "RS",
+            RTH => "RTH",
+            RTS => "RTS",
+            SC => "SC",
+            STATPVT => "STATPVT",
+            STAT => "STAT",
+            Unknown(code) => code,
         }
     }
+
+    /// Looks up a known GPO code, ignoring case. Use `parse` to keep unknown codes.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|version| version.code().eq_ignore_ascii_case(code))
+    }
+
+    /// The chamber the version was printed in. Enrolled bills and laws belong to both.
+    pub fn chamber(&self) -> Chamber {
+        use BillTextVersionAbbreviation::*;
+        match self {
+            ASH | ATH | CDH | CPH | EAH | EH | FPH | IH | IPH | LTH | RCH | RFH | RH | RHUC
+            | RIH | RTH => Chamber::House,
+            AS | ATS | CDS | CPS | EAS | ES | FPS | HDS | IPS | IS | LTS | PCS | RCS | RDS
+            | RFS | RS | RTS => Chamber::Senate,
+            ENR | PAP | PP | PL | PVTL | SC | STAT | STATPVT | Unknown(_) => Chamber::Both,
+        }
+    }
+
+    /// How far the bill had gotten when this version was printed. Versions for failed
+    /// floor votes stay at the stage they reached. `None` for unknown codes.
+    pub fn stage(&self) -> Option<LegislativeStage> {
+        use BillTextVersionAbbreviation::*;
+        use LegislativeStage::*;
+        Some(match self {
+            IH | IS | ASH | SC => Introduced,
+            RTH | RTS | RCH | RCS | RIH => Referred,
+            RH | RS | RHUC | CDH | CDS | PCS | AS | FPH | FPS | IPH | IPS | LTH | LTS => Reported,
+            EH | ES | RDS | HDS | RFH | RFS | PAP | PP => Engrossed,
+            EAH | EAS | ATH | ATS | CPH | CPS => PassedOtherChamber,
+            ENR => Enrolled,
+            PL | PVTL | STAT | STATPVT => Law,
+            Unknown(_) => return None,
+        })
+    }
+}
+
+impl FromStr for BillTextVersionAbbreviation {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.trim();
+        Ok(Self::from_code(code).unwrap_or_else(|| Self::Unknown(code.to_uppercase())))
+    }
+}
+
+impl Serialize for BillTextVersionAbbreviation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for BillTextVersionAbbreviation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(code.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+impl IntoView for BillTextVersionAbbreviation {
+    fn into_view(self) -> leptos::View {
+        self.to_string().into_view()
+    }
+}
+
+/// Steps a bill goes through on its way to becoming law, in order.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LegislativeStage {
+    Introduced,
+    Referred,
+    Reported,
+    Engrossed,
+    PassedOtherChamber,
+    Enrolled,
+    Law,
+}
+
+impl Display for LegislativeStage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use LegislativeStage::*;
+        match self {
+            Introduced => write!(f, "Introduced"),
+            Referred => write!(f, "Referred to Committee"),
+            Reported => write!(f, "Reported by Committee"),
+            Engrossed => write!(f, "Passed One Chamber"),
+            PassedOtherChamber => write!(f, "Passed Both Chambers"),
+            Enrolled => write!(f, "Sent to the President"),
+            Law => write!(f, "Became Law"),
+        }
+    }
+}
+
+impl IntoView for LegislativeStage {
+    fn into_view(self) -> leptos::View {
+        self.to_string().into_view()
+    }
+}
+
+impl LegislativeStage {
+    pub const ALL: [LegislativeStage; 7] = {
+        use LegislativeStage::*;
+        [
+            Introduced,
+            Referred,
+            Reported,
+            Engrossed,
+            PassedOtherChamber,
+            Enrolled,
+            Law,
+        ]
+    };
+
+    /// The furthest stage any of the versions reached.
+    pub fn reached<'a>(
+        versions: impl IntoIterator<Item = &'a BillTextVersionAbbreviation>,
+    ) -> Option<Self> {
+        versions
+            .into_iter()
+            .filter_map(|version| version.stage())
+            .max()
+    }
+
+    /// Position in `ALL`, starting at 1 for `Introduced`.
+    pub fn step(&self) -> usize {
+        *self as usize + 1
+    }
+
+    /// The serialized name, used in URLs and the database, e.g. `passed_other_chamber`.
+    pub fn code(&self) -> &'static str {
+        use LegislativeStage::*;
+        match self {
+            Introduced => "introduced",
+            Referred => "referred",
+            Reported => "reported",
+            Engrossed => "engrossed",
+            PassedOtherChamber => "passed_other_chamber",
+            Enrolled => "enrolled",
+            Law => "law",
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("'{0}' is not a legislative stage")]
+pub struct LegislativeStageError(pub String);
+
+/// Accepts the code ignoring case, with `-` or `_` between words.
+impl FromStr for LegislativeStage {
+    type Err = LegislativeStageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.trim().to_lowercase().replace('-', "_");
+        Self::ALL
+            .into_iter()
+            .find(|stage| stage.code() == code)
+            .ok_or_else(|| LegislativeStageError(s.trim().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_congress_on() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(congress_on(date(2024, 3, 1)), 118);
+        assert_eq!(congress_on(date(2023, 1, 3)), 118);
+        assert_eq!(congress_on(date(2023, 1, 2)), 117);
+        assert_eq!(congress_on(date(1789, 3, 4)), 1);
+    }
+
+    #[test]
+    fn test_action_chamber() {
+        let action: BillAction = serde_json::from_str(
+            r#"{
+                "actionCode": "H11100",
+                "actionDate": "2024-02-06",
+                "sourceSystem": { "code": 2, "name": "House floor actions" },
+                "text": "Referred to the Committee on Education and the Workforce.",
+                "type": "IntroReferral"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(action.chamber(), Chamber::House);
+        assert!(action.recorded_votes.is_empty());
+    }
+
+    #[test]
+    fn test_text_version_abbreviation() {
+        let version = TextVersion {
+            date: Some("2024-02-06T05:00:00Z".to_string()),
+            version_type: "Introduced in House".to_string(),
+            formats: vec![TextFormat {
+                format_type: "Formatted Text".to_string(),
+                url: "https://www.congress.gov/118/bills/hr7261/BILLS-118hr7261ih.htm".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            version.abbreviation(),
+            Some(BillTextVersionAbbreviation::IH)
+        );
+        assert_eq!(
+            BillTextVersionAbbreviation::from_code("eas"),
+            Some(BillTextVersionAbbreviation::EAS)
+        );
+        assert_eq!(BillTextVersionAbbreviation::from_code("xx"), None);
+    }
+
+    #[test]
+    fn test_sort_text_versions() {
+        let version = |code: &str, date: &str| TextVersion {
+            date: Some(date.to_string()),
+            version_type: String::new(),
+            formats: vec![TextFormat {
+                format_type: "Formatted Text".to_string(),
+                url: format!("https://www.congress.gov/118/bills/hr7261/BILLS-118hr7261{code}.htm"),
+            }],
+        };
+        // Engrossed and reported on the same day, an unknown code, and the introduced text last.
+        let mut versions = vec![
+            version("eh", "2024-03-01"),
+            version("xx", "2024-01-01"),
+            version("rh", "2024-03-01"),
+            version("ih", "2024-02-06"),
+        ];
+        TextVersion::sort(&mut versions);
+
+        let abbreviations: Vec<_> = versions.iter().map(TextVersion::abbreviation).collect();
+        assert_eq!(
+            abbreviations,
+            [
+                Some(BillTextVersionAbbreviation::IH),
+                Some(BillTextVersionAbbreviation::RH),
+                Some(BillTextVersionAbbreviation::EH),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_version_serde() {
+        let versions: Vec<BillTextVersionAbbreviation> =
+            serde_json::from_str(r#"["IH", "enr", "XYZ"]"#).unwrap();
+
+        assert_eq!(
+            versions,
+            vec![
+                BillTextVersionAbbreviation::IH,
+                BillTextVersionAbbreviation::ENR,
+                BillTextVersionAbbreviation::Unknown("XYZ".to_string()),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&versions).unwrap(),
+            r#"["IH","ENR","XYZ"]"#
+        );
+        assert_eq!(
+            "rh".parse::<BillTextVersionAbbreviation>()
+                .unwrap()
+                .to_string(),
+            "Reported in House"
+        );
+    }
+
+    #[test]
+    fn test_bill_type_parsing() {
+        for spelling in ["HR", "hr", "H.R.", "H. R.", "House Bill"] {
+            assert_eq!(spelling.parse(), Ok(BillType::HR));
+        }
+        assert_eq!("H. Res.".parse(), Ok(BillType::HRES));
+        assert_eq!("hconres".parse(), Ok(BillType::HCONRES));
+        assert_eq!("S.Con.Res.".parse(), Ok(BillType::SCONRES));
+        assert_eq!("Senate Joint Resolution".parse(), Ok(BillType::SJRES));
+        assert_eq!(
+            "PL".parse::<BillType>(),
+            Err(BillTypeError("PL".to_string()))
+        );
+
+        for bill_type in BillType::ALL {
+            assert_eq!(bill_type.slug().parse(), Ok(bill_type));
+            assert_eq!(bill_type.citation().parse(), Ok(bill_type));
+        }
+    }
+
+    #[test]
+    fn test_bill_type_api() {
+        let item: BillListItem = serde_json::from_str(
+            r#"{
+                "congress": 118,
+                "number": "7261",
+                "title": "Reimagining Inclusive Arts Education Act",
+                "type": "HR"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.bill_type, BillType::HR);
+        assert_eq!(
+            serde_json::to_value(&item).unwrap()["type"],
+            serde_json::json!("HR")
+        );
+        assert_eq!(BillType::HR.chamber(), Chamber::House);
+        assert_eq!(BillType::SCONRES.chamber(), Chamber::Senate);
+        assert!(BillType::HJRES.is_joint() && BillType::HJRES.is_resolution());
+        assert!(!BillType::S.is_resolution());
+        assert_eq!(BillType::SJRES.long_name(), "Senate Joint Resolution");
+    }
+
+    #[test]
+    fn test_bill_id_parsing() {
+        let hr = BillId::new(118, BillType::HR, 7261);
+
+        assert_eq!("hr7261-118".parse(), Ok(hr));
+        assert_eq!("118-hr-7261".parse(), Ok(hr));
+        assert_eq!("H.R. 7261 (118th)".parse(), Ok(hr));
+        assert_eq!(
+            "H.R. 7261".parse::<BillId>().unwrap().congress,
+            current_congress()
+        );
+        assert_eq!(
+            "S.J.Res. 12 (118th)".parse(),
+            Ok(BillId::new(118, BillType::SJRES, 12))
+        );
+        assert_eq!(
+            "H. Con. Res. 3 (117th Congress)".parse(),
+            Ok(BillId::new(117, BillType::HCONRES, 3))
+        );
+        assert!(matches!(
+            "X.Y. 12".parse::<BillId>(),
+            Err(BillIdError::BillType { .. })
+        ));
+        assert!(matches!(
+            "Reimagining Act".parse::<BillId>(),
+            Err(BillIdError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_bill_id_forms() {
+        let id = BillId::new(118, BillType::SJRES, 12);
+
+        assert_eq!(id.to_string(), "S.J.Res. 12 (118th)");
+        assert_eq!(id.key(), "118-sjres-12");
+        assert_eq!(id.url(), "/bills/118/sjres/12");
+        assert_eq!(id.to_string().parse(), Ok(id));
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""118-sjres-12""#);
+        assert_eq!(
+            serde_json::from_str::<BillId>(r#""118-sjres-12""#).unwrap(),
+            id
+        );
+
+        let thing = id.thing();
+        assert_eq!(thing.to_string(), "bill:⟨118-sjres-12⟩");
+        assert_eq!(BillId::try_from(&thing), Ok(id));
+        assert!(BillId::try_from(Thing::from(("person", "118-sjres-12"))).is_err());
+    }
+
+    #[test]
+    fn test_legislative_stage() {
+        use BillTextVersionAbbreviation::*;
+
+        assert_eq!(IH.chamber(), Chamber::House);
+        assert_eq!(EAS.chamber(), Chamber::Senate);
+        assert_eq!(ENR.chamber(), Chamber::Both);
+        assert_eq!(RH.stage(), Some(LegislativeStage::Reported));
+        assert_eq!(Unknown("XYZ".to_string()).stage(), None);
+        assert!(LegislativeStage::Introduced < LegislativeStage::Law);
+        assert_eq!(
+            LegislativeStage::reached(&[IH, RH, EH, RFS]),
+            Some(LegislativeStage::Engrossed)
+        );
+        assert_eq!(LegislativeStage::reached(&[]), None);
+        assert_eq!(LegislativeStage::Law.step(), LegislativeStage::ALL.len());
+        assert_eq!(
+            "passed-other-chamber".parse(),
+            Ok(LegislativeStage::PassedOtherChamber)
+        );
+        for stage in LegislativeStage::ALL {
+            assert_eq!(stage.code().parse(), Ok(stage));
+            assert_eq!(
+                serde_json::to_string(&stage).unwrap(),
+                format!("\"{}\"", stage.code())
+            );
+        }
+
+        for version in BillTextVersionAbbreviation::ALL {
+            assert!(version.stage().is_some(), "{version:?} has no stage");
+        }
+    }
+
+    #[test]
+    fn test_bill_list_query() {
+        let params = [
+            ("congress", "118"),
+            ("type", "HR"),
+            ("page", "3"),
+            ("sort", "updateDate"),
+        ];
+        let get = |key: &str| {
+            params
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| *value)
+        };
+        let query = BillListQuery::from_params(get);
+        assert_eq!(query.congress, Some(118));
+        assert_eq!(query.bill_type, Some(BillType::HR));
+        assert_eq!(query.sort, BillSort::Oldest);
+        assert_eq!(query.offset(), 40);
+        assert_eq!(
+            query.to_query_string(),
+            "?congress=118&type=hr&page=3&sort=updateDate"
+        );
+        assert_eq!(
+            query.with_page(1).to_query_string(),
+            "?congress=118&type=hr&sort=updateDate"
+        );
+
+        // Bad values fall back to the defaults.
+        let params = [
+            ("congress", "next"),
+            ("type", "bogus"),
+            ("page", "0"),
+            ("sort", "title"),
+        ];
+        let get = |key: &str| {
+            params
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| *value)
+        };
+        assert_eq!(BillListQuery::from_params(get), BillListQuery::default());
+        assert_eq!(BillListQuery::default().to_query_string(), "");
+        assert_eq!(BillSort::from_param("-updateDate"), Some(BillSort::Newest));
+
+        let get = |key: &str| (key == "page").then_some("4294967295");
+        let query = BillListQuery::from_params(get);
+        assert_eq!(query.page, u32::MAX);
+        assert_eq!(query.offset(), u32::MAX);
+
+        assert_eq!(BillListQuery::pages(0), 1);
+        assert_eq!(BillListQuery::pages(20), 1);
+        assert_eq!(BillListQuery::pages(21), 2);
+    }
+
+    #[test]
+    fn test_ordinal() {
+        assert_eq!(ordinal(118), "118th");
+        assert_eq!(ordinal(121), "121st");
+        assert_eq!(ordinal(111), "111th");
+        assert_eq!(ordinal(102), "102nd");
+    }
 }
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// A committee as listed by the Congress.gov `/committee` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Committee {
+    pub system_code: String,
+    pub name: String,
+    pub chamber: Option<String>,
+    pub committee_type_code: Option<String>,
+}
@@ -0,0 +1,163 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Datetime;
+
+use crate::domain::bills::{current_congress, BillId, BillTextVersionAbbreviation, BillType};
+use crate::domain::feeds::FeedErrors;
+
+/// `H.R. 7261 (IH) - Title`. GovInfo separates the parts with plain or non-breaking spaces,
+/// and some titles have no version code or use an en dash.
+static BILL_TITLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?<bill_type>[A-Za-z][A-Za-z.\s]*?)\s*(?<bill_number>\d+)\s*(?:\((?<bill_version>[A-Za-z]+)\))?\s*(?:[-–—]\s*(?<title>.*?))?\s*$",
+    )
+    .unwrap()
+});
+
+/// The Congress in a GovInfo package link, e.g. `https://www.govinfo.gov/app/details/BILLS-118hr7261ih`.
+static PACKAGE_CONGRESS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"BILLS-(?<congress>\d+)[a-z]").unwrap());
+
+/// A bill from the GovInfo bills RSS feed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BillItem {
+    pub congress: u32,
+    pub bill_type: BillType,
+    pub bill_number: i32,
+    /// Missing from a few titles.
+    pub bill_version: Option<BillTextVersionAbbreviation>,
+    pub title: String,
+}
+
+impl BillItem {
+    pub fn id(&self) -> BillId {
+        BillId::new(self.congress, self.bill_type, self.bill_number as u32)
+    }
+}
+
+/// A bill feed item as stored in the `feed_item` table.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedEntry {
+    pub item: BillItem,
+    pub guid: String,
+    pub link: Option<String>,
+    pub first_seen: Datetime,
+    /// First seen after the reader's previous visit. Computed when reading, not stored.
+    #[serde(default)]
+    pub is_new: bool,
+}
+
+/// Parses an unescaped feed item title, taking the Congress from the item link when there is one.
+pub fn parse_bill_item(input: &str, link: Option<&str>) -> Result<BillItem, FeedErrors> {
+    let caps = BILL_TITLE
+        .captures(input)
+        .ok_or_else(|| FeedErrors::MatchError {
+            title: input.to_string(),
+        })?;
+
+    let bill_type_match = caps.name("bill_type").unwrap();
+    let bill_type =
+        bill_type_match
+            .as_str()
+            .parse::<BillType>()
+            .map_err(|_| FeedErrors::BillTypeError {
+                found: bill_type_match.as_str().trim().to_string(),
+                position: bill_type_match.start(),
+                title: input.to_string(),
+            })?;
+
+    let bill_number_match = caps.name("bill_number").unwrap();
+    let bill_number =
+        bill_number_match
+            .as_str()
+            .parse::<i32>()
+            .map_err(|_| FeedErrors::ParseError {
+                found: bill_number_match.as_str().to_string(),
+                position: bill_number_match.start(),
+                title: input.to_string(),
+            })?;
+
+    let congress = link
+        .and_then(|link| PACKAGE_CONGRESS.captures(link))
+        .and_then(|caps| caps["congress"].parse().ok())
+        .unwrap_or_else(current_congress);
+
+    Ok(BillItem {
+        congress,
+        bill_type,
+        bill_number,
+        bill_version: caps
+            .name("bill_version")
+            .and_then(|version| version.as_str().parse().ok()),
+        title: caps
+            .name("title")
+            .map(|title| title.as_str().to_string())
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bill_type_parsing() {
+        let input = "H.R. 7261 (IH) - Reimagining Inclusive Arts Education Act";
+        let item = parse_bill_item(
+            input,
+            Some("https://www.govinfo.gov/app/details/BILLS-118hr7261ih"),
+        )
+        .unwrap();
+
+        assert_eq!(item.congress, 118);
+        assert!(matches!(item.bill_type, BillType::HR));
+        assert_eq!(item.bill_number, 7261);
+        assert_eq!(item.bill_version, Some(BillTextVersionAbbreviation::IH));
+        assert_eq!(item.id().key(), "118-hr-7261");
+        assert_eq!(item.title, "Reimagining Inclusive Arts Education Act");
+    }
+
+    #[test]
+    fn test_title_variants() {
+        let joint = parse_bill_item(
+            "S.J.\u{a0}Res.\u{a0}12\u{a0}(IS) - Providing for congressional disapproval",
+            None,
+        )
+        .unwrap();
+        assert!(matches!(joint.bill_type, BillType::SJRES));
+        assert_eq!(joint.bill_number, 12);
+
+        let concurrent = parse_bill_item(
+            "H. Con. Res. 9 (EH) – Authorizing the use of the rotunda",
+            None,
+        )
+        .unwrap();
+        assert!(matches!(concurrent.bill_type, BillType::HCONRES));
+        assert_eq!(concurrent.title, "Authorizing the use of the rotunda");
+
+        let private = parse_bill_item("H.R. 7262 - For the relief of Jane Doe", None).unwrap();
+        assert_eq!(private.bill_version, None);
+        assert_eq!(private.title, "For the relief of Jane Doe");
+    }
+
+    #[test]
+    fn test_rejected_titles() {
+        assert_eq!(
+            parse_bill_item("No title found for Bill.", None),
+            Err(FeedErrors::MatchError {
+                title: "No title found for Bill.".to_string()
+            })
+        );
+
+        let error =
+            parse_bill_item("H. Doc. 118 (PP) - Budget of the United States", None).unwrap_err();
+        assert!(matches!(
+            error,
+            FeedErrors::BillTypeError { ref found, position: 0, .. } if found == "H. Doc."
+        ));
+
+        let error = parse_bill_item("H.R. 99999999999 (IH) - Too big", None).unwrap_err();
+        assert!(matches!(error, FeedErrors::ParseError { position: 5, .. }));
+    }
+}
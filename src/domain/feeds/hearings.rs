@@ -0,0 +1,61 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::bills::Chamber;
+use crate::domain::feeds::{package_number, FeedErrors};
+
+/// `https://www.govinfo.gov/app/details/CHRG-118hhrg54321`, `j` for joint hearings.
+static HEARING_PACKAGE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"CHRG-(?<congress>\d+)(?<chamber>[hsj])hrg(?<jacket>\d+)").unwrap());
+
+/// A hearing from the GovInfo congressional hearings (CHRG) RSS feed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HearingItem {
+    pub congress: u32,
+    /// `Both` for joint hearings.
+    pub chamber: Chamber,
+    /// GPO jacket number, kept as text since it may have leading zeros.
+    pub jacket: String,
+    pub title: String,
+}
+
+pub fn parse_hearing_item(input: &str, link: Option<&str>) -> Result<HearingItem, FeedErrors> {
+    let caps = link
+        .and_then(|link| HEARING_PACKAGE.captures(link))
+        .ok_or_else(|| FeedErrors::MatchError {
+            title: input.to_string(),
+        })?;
+
+    Ok(HearingItem {
+        congress: package_number(&caps, "congress", input)?,
+        chamber: match &caps["chamber"] {
+            "h" => Chamber::House,
+            "s" => Chamber::Senate,
+            _ => Chamber::Both,
+        },
+        jacket: caps["jacket"].to_string(),
+        title: input.trim().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hearing_item() {
+        let item = parse_hearing_item(
+            " Oversight of the Federal Aviation Administration ",
+            Some("https://www.govinfo.gov/app/details/CHRG-118shrg55012"),
+        )
+        .unwrap();
+
+        assert_eq!(item.chamber, Chamber::Senate);
+        assert_eq!(item.jacket, "55012");
+        assert_eq!(
+            item.title,
+            "Oversight of the Federal Aviation Administration"
+        );
+    }
+}
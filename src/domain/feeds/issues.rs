@@ -0,0 +1,70 @@
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::feeds::FeedErrors;
+
+/// `https://www.govinfo.gov/app/details/CREC-2024-03-05`, same for `FR-`.
+static ISSUE_PACKAGE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:CREC|FR)-(?<date>\d{4}-\d{2}-\d{2})").unwrap());
+
+/// `Congressional Record, Volume 170 Issue 40 (Tuesday, March 5, 2024)`
+static VOLUME_ISSUE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Volume\s+(?<volume>\d+),?\s+Issue\s+(?<issue>\d+)").unwrap());
+
+/// A daily issue from the Congressional Record (CREC) or Federal Register (FR) RSS feeds.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IssueItem {
+    pub date: NaiveDate,
+    pub volume: Option<u32>,
+    pub issue: Option<u32>,
+    pub title: String,
+}
+
+/// The issue date comes from the package id in the link, volume and issue from the title when it has them.
+pub fn parse_issue_item(input: &str, link: Option<&str>) -> Result<IssueItem, FeedErrors> {
+    let match_error = || FeedErrors::MatchError {
+        title: input.to_string(),
+    };
+    let caps = link
+        .and_then(|link| ISSUE_PACKAGE.captures(link))
+        .ok_or_else(match_error)?;
+    let date = NaiveDate::parse_from_str(&caps["date"], "%Y-%m-%d").map_err(|_| match_error())?;
+    let volume_issue = VOLUME_ISSUE.captures(input);
+
+    Ok(IssueItem {
+        date,
+        volume: volume_issue
+            .as_ref()
+            .and_then(|caps| caps["volume"].parse().ok()),
+        issue: volume_issue
+            .as_ref()
+            .and_then(|caps| caps["issue"].parse().ok()),
+        title: input.trim().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_item() {
+        let item = parse_issue_item(
+            "Congressional Record, Volume 170 Issue 40 (Tuesday, March 5, 2024)",
+            Some("https://www.govinfo.gov/app/details/CREC-2024-03-05"),
+        )
+        .unwrap();
+
+        assert_eq!(item.date, NaiveDate::from_ymd_opt(2024, 3, 5).unwrap());
+        assert_eq!((item.volume, item.issue), (Some(170), Some(40)));
+
+        let register = parse_issue_item(
+            "Federal Register",
+            Some("https://www.govinfo.gov/app/details/FR-2024-03-06"),
+        )
+        .unwrap();
+        assert_eq!(register.volume, None);
+    }
+}
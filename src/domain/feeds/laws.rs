@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::feeds::{package_number, package_title, FeedErrors};
+
+/// `https://www.govinfo.gov/app/details/PLAW-118publ35`
+static LAW_PACKAGE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"PLAW-(?<congress>\d+)(?<kind>publ|pvtl)(?<number>\d+)").unwrap());
+
+/// `Public Law 118 - 35 - ` in front of the title.
+static LAW_PREFIX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:Public|Private)\s+Law\s+\d+\s*-\s*\d+\s*[-–—:]?\s*").unwrap());
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LawKind {
+    Public,
+    Private,
+}
+
+/// A law from the GovInfo public and private laws (PLAW) RSS feed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LawItem {
+    pub congress: u32,
+    pub kind: LawKind,
+    pub number: u32,
+    pub title: String,
+}
+
+/// `Pub. L. 118-35`
+impl Display for LawItem {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let kind = match self.kind {
+            LawKind::Public => "Pub. L.",
+            LawKind::Private => "Pvt. L.",
+        };
+        write!(f, "{kind} {}-{}", self.congress, self.number)
+    }
+}
+
+/// The law number comes from the package id in the link, titles don't always repeat it.
+pub fn parse_law_item(input: &str, link: Option<&str>) -> Result<LawItem, FeedErrors> {
+    let caps = link
+        .and_then(|link| LAW_PACKAGE.captures(link))
+        .ok_or_else(|| FeedErrors::MatchError {
+            title: input.to_string(),
+        })?;
+
+    Ok(LawItem {
+        congress: package_number(&caps, "congress", input)?,
+        kind: match &caps["kind"] {
+            "publ" => LawKind::Public,
+            _ => LawKind::Private,
+        },
+        number: package_number(&caps, "number", input)?,
+        title: package_title(&LAW_PREFIX, input),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_law_item() {
+        let item = parse_law_item(
+            "Public Law 118 - 35 - Further Additional Continuing Appropriations",
+            Some("https://www.govinfo.gov/app/details/PLAW-118publ35"),
+        )
+        .unwrap();
+
+        assert_eq!(item.kind, LawKind::Public);
+        assert_eq!(item.to_string(), "Pub. L. 118-35");
+        assert_eq!(item.title, "Further Additional Continuing Appropriations");
+        assert!(parse_law_item("Public Law 118 - 35", None).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+use bills::BillItem;
+use hearings::HearingItem;
+use issues::IssueItem;
+use laws::LawItem;
+use reports::ReportItem;
+
+pub mod bills;
+pub mod hearings;
+pub mod issues;
+pub mod laws;
+pub mod polling;
+pub mod reports;
+
+/// A GovInfo collection with an RSS feed. Each one has its own item parser and card.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Collection {
+    Bills,
+    PublicLaws,
+    CongressionalRecord,
+    FederalRegister,
+    CommitteeReports,
+    Hearings,
+}
+
+impl Collection {
+    pub const ALL: [Collection; 6] = [
+        Collection::Bills,
+        Collection::PublicLaws,
+        Collection::CongressionalRecord,
+        Collection::FederalRegister,
+        Collection::CommitteeReports,
+        Collection::Hearings,
+    ];
+
+    /// The GovInfo collection code, e.g. `PLAW`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Collection::Bills => "BILLS",
+            Collection::PublicLaws => "PLAW",
+            Collection::CongressionalRecord => "CREC",
+            Collection::FederalRegister => "FR",
+            Collection::CommitteeReports => "CRPT",
+            Collection::Hearings => "CHRG",
+        }
+    }
+
+    /// Used in `/feeds/:collection` and the RSS file name.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Collection::Bills => "bills",
+            Collection::PublicLaws => "plaw",
+            Collection::CongressionalRecord => "crec",
+            Collection::FederalRegister => "fr",
+            Collection::CommitteeReports => "crpt",
+            Collection::Hearings => "chrg",
+        }
+    }
+
+    pub fn rss_url(&self) -> String {
+        format!("https://www.govinfo.gov/rss/{}.xml", self.slug())
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Collection::Bills => "Every printed version of House and Senate bills and resolutions.",
+            Collection::PublicLaws => "Public and private laws as enacted.",
+            Collection::CongressionalRecord => "Daily proceedings and debates of Congress.",
+            Collection::FederalRegister => {
+                "Daily rules, proposed rules and notices of federal agencies."
+            }
+            Collection::CommitteeReports => "House, Senate and executive committee reports.",
+            Collection::Hearings => "Published transcripts of committee hearings.",
+        }
+    }
+
+    /// Parses an unescaped feed item title with this collection's parser.
+    pub fn parse_item(&self, title: &str, link: Option<&str>) -> Result<FeedItem, FeedErrors> {
+        Ok(match self {
+            Collection::Bills => FeedItem::Bill(bills::parse_bill_item(title, link)?),
+            Collection::PublicLaws => FeedItem::Law(laws::parse_law_item(title, link)?),
+            Collection::CongressionalRecord => {
+                FeedItem::Record(issues::parse_issue_item(title, link)?)
+            }
+            Collection::FederalRegister => {
+                FeedItem::FederalRegister(issues::parse_issue_item(title, link)?)
+            }
+            Collection::CommitteeReports => {
+                FeedItem::Report(reports::parse_report_item(title, link)?)
+            }
+            Collection::Hearings => FeedItem::Hearing(hearings::parse_hearing_item(title, link)?),
+        })
+    }
+}
+
+impl Display for Collection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Collection::Bills => write!(f, "Congressional Bills"),
+            Collection::PublicLaws => write!(f, "Public and Private Laws"),
+            Collection::CongressionalRecord => write!(f, "Congressional Record"),
+            Collection::FederalRegister => write!(f, "Federal Register"),
+            Collection::CommitteeReports => write!(f, "Congressional Reports"),
+            Collection::Hearings => write!(f, "Congressional Hearings"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCollection(pub String);
+
+impl Display for UnknownCollection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown GovInfo collection {:?}.", self.0)
+    }
+}
+
+impl std::error::Error for UnknownCollection {}
+
+/// Accepts the slug or the collection code, ignoring case.
+impl FromStr for Collection {
+    type Err = UnknownCollection;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|collection| {
+                collection.slug().eq_ignore_ascii_case(s)
+                    || collection.code().eq_ignore_ascii_case(s)
+            })
+            .ok_or_else(|| UnknownCollection(s.to_string()))
+    }
+}
+
+/// An item from any of the GovInfo feeds.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum FeedItem {
+    Bill(BillItem),
+    Law(LawItem),
+    Record(IssueItem),
+    FederalRegister(IssueItem),
+    Report(ReportItem),
+    Hearing(HearingItem),
+}
+
+/// A number captured from a package id, e.g. the Congress in `PLAW-118publ35`.
+fn package_number(caps: &Captures, name: &str, title: &str) -> Result<u32, FeedErrors> {
+    let found = caps
+        .name(name)
+        .expect("package regexes capture every number they use");
+    found.as_str().parse().map_err(|_| FeedErrors::ParseError {
+        found: found.as_str().to_string(),
+        position: found.start(),
+        title: title.to_string(),
+    })
+}
+
+/// The title without a citation prefix such as `Public Law 118 - 35 - `.
+fn package_title(prefix: &Regex, title: &str) -> String {
+    prefix.replace(title, "").trim().to_string()
+}
+
+/// Why a GovInfo feed item was rejected. Every variant keeps the raw title for logging.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum FeedErrors {
+    /// The title doesn't have the `<type> <number> (<version>) - <title>` shape at all.
+    MatchError { title: String },
+    /// The bill type found at `position` is not one we know.
+    BillTypeError {
+        found: String,
+        position: usize,
+        title: String,
+    },
+    /// The number found at `position` doesn't fit, in the title for bills and in the link otherwise.
+    ParseError {
+        found: String,
+        position: usize,
+        title: String,
+    },
+}
+
+impl FeedErrors {
+    pub fn title(&self) -> &str {
+        match self {
+            FeedErrors::MatchError { title }
+            | FeedErrors::BillTypeError { title, .. }
+            | FeedErrors::ParseError { title, .. } => title,
+        }
+    }
+}
+
+impl Display for FeedErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeedErrors::MatchError { title } => {
+                write!(f, "Error matching feed title {title:?}.")
+            }
+            FeedErrors::BillTypeError {
+                found,
+                position,
+                title,
+            } => write!(
+                f,
+                "Error matching Bill Type {found:?} at {position} in {title:?}."
+            ),
+            FeedErrors::ParseError {
+                found,
+                position,
+                title,
+            } => write!(
+                f,
+                "Error parsing number {found:?} at {position} for {title:?}."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FeedErrors {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection_registry() {
+        for collection in Collection::ALL {
+            assert_eq!(collection.slug().parse(), Ok(collection));
+            assert_eq!(collection.code().parse(), Ok(collection));
+        }
+        assert_eq!(
+            Collection::CommitteeReports.rss_url(),
+            "https://www.govinfo.gov/rss/crpt.xml"
+        );
+        assert!("USCODE".parse::<Collection>().is_err());
+
+        assert!(matches!(
+            Collection::PublicLaws.parse_item(
+                "Public Law 118 - 35",
+                Some("https://www.govinfo.gov/app/details/PLAW-118publ35")
+            ),
+            Ok(FeedItem::Law(_))
+        ));
+        assert!(matches!(
+            Collection::Hearings.parse_item(
+                "Hearing",
+                Some("https://www.govinfo.gov/app/details/BILLS-118hr1ih")
+            ),
+            Err(FeedErrors::MatchError { .. })
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Datetime;
+
+/// Conditional GET state of a polled feed, stored in the `feed_source` table keyed by collection slug.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedSource {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// The `<ttl>` the feed asks for, in minutes.
+    pub ttl_minutes: Option<u32>,
+    /// Failed polls in a row, drives the backoff.
+    pub failures: u32,
+    pub next_poll: Datetime,
+}
+
+impl FeedSource {
+    /// A feed that was never polled, due right away.
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            etag: None,
+            last_modified: None,
+            ttl_minutes: None,
+            failures: 0,
+            next_poll: Datetime::from(chrono::Utc::now()),
+        }
+    }
+}
+
+/// One request the poller made, stored in the `feed_fetch` table.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedFetch {
+    /// Slug of the polled collection.
+    pub source: String,
+    pub url: String,
+    pub fetched_at: Datetime,
+    /// HTTP status, `None` when no response arrived.
+    pub status: Option<u16>,
+    pub bytes: u64,
+    pub items: u32,
+    pub rejected: u32,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+impl FeedFetch {
+    pub fn not_modified(&self) -> bool {
+        self.status == Some(304)
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::feeds::{package_number, package_title, FeedErrors};
+
+/// `https://www.govinfo.gov/app/details/CRPT-118hrpt400`
+static REPORT_PACKAGE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"CRPT-(?<congress>\d+)(?<kind>hrpt|srpt|erpt)(?<number>\d+)").unwrap()
+});
+
+/// `H. Rept. 118-400 - ` in front of the title.
+static REPORT_PREFIX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:H\.|S\.|Exec\.)\s*Rept\.\s*\d+\s*-\s*\d+\s*[-–—:]?\s*").unwrap()
+});
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ReportKind {
+    House,
+    Senate,
+    /// Senate reports on treaties and nominations.
+    Executive,
+}
+
+/// A committee report from the GovInfo congressional reports (CRPT) RSS feed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReportItem {
+    pub congress: u32,
+    pub kind: ReportKind,
+    pub number: u32,
+    pub title: String,
+}
+
+/// `H. Rept. 118-400`
+impl Display for ReportItem {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let kind = match self.kind {
+            ReportKind::House => "H. Rept.",
+            ReportKind::Senate => "S. Rept.",
+            ReportKind::Executive => "Exec. Rept.",
+        };
+        write!(f, "{kind} {}-{}", self.congress, self.number)
+    }
+}
+
+pub fn parse_report_item(input: &str, link: Option<&str>) -> Result<ReportItem, FeedErrors> {
+    let caps = link
+        .and_then(|link| REPORT_PACKAGE.captures(link))
+        .ok_or_else(|| FeedErrors::MatchError {
+            title: input.to_string(),
+        })?;
+
+    Ok(ReportItem {
+        congress: package_number(&caps, "congress", input)?,
+        kind: match &caps["kind"] {
+            "hrpt" => ReportKind::House,
+            "srpt" => ReportKind::Senate,
+            _ => ReportKind::Executive,
+        },
+        number: package_number(&caps, "number", input)?,
+        title: package_title(&REPORT_PREFIX, input),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_item() {
+        let item = parse_report_item(
+            "H. Rept. 118-400 - Providing for consideration of the bill (H.R. 7521)",
+            Some("https://www.govinfo.gov/app/details/CRPT-118hrpt400"),
+        )
+        .unwrap();
+
+        assert_eq!(item.to_string(), "H. Rept. 118-400");
+        assert_eq!(
+            item.title,
+            "Providing for consideration of the bill (H.R. 7521)"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+/// A member who cosponsored bills that another member sponsored or cosponsored.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Collaborator {
+    pub bioguide_id: String,
+    /// `None` for members that were never synced.
+    pub name: Option<String>,
+    pub party: Option<String>,
+    /// Bills both members are on.
+    pub shared: u32,
+}
+
+impl Collaborator {
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.bioguide_id)
+    }
+}
+
+/// How many of a bill's current cosponsors are from outside the sponsor's party.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Bipartisanship {
+    pub cosponsors: u32,
+    pub across: u32,
+}
+
+impl Bipartisanship {
+    /// Between 0 and 1, `None` without cosponsors.
+    pub fn share(&self) -> Option<f64> {
+        (self.cosponsors > 0).then(|| f64::from(self.across) / f64::from(self.cosponsors))
+    }
+}
+
+/// Fill color of a party, by code ("D") or name ("Democratic").
+pub fn party_color(party: Option<&str>) -> &'static str {
+    match party.and_then(|party| party.chars().next()) {
+        Some('D') => "#2563eb",
+        Some('R') => "#dc2626",
+        Some('I') => "#7c3aed",
+        _ => "#9ca3af",
+    }
+}
+
+/// A collaborator placed around the member in the middle of the network view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkNode {
+    pub collaborator: Collaborator,
+    pub x: f64,
+    pub y: f64,
+    /// `shared` relative to the strongest tie, between 0 and 1.
+    pub weight: f64,
+}
+
+/// Places collaborators evenly on a circle around the center of a `size` square, clockwise from
+/// the top in the given order.
+pub fn network_layout(collaborators: &[Collaborator], size: f64) -> Vec<NetworkNode> {
+    let center = size / 2.0;
+    let radius = size * 0.38;
+    let strongest = collaborators
+        .iter()
+        .map(|collaborator| collaborator.shared)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    collaborators
+        .iter()
+        .enumerate()
+        .map(|(i, collaborator)| {
+            let angle = 2.0 * PI * i as f64 / collaborators.len() as f64 - PI / 2.0;
+            NetworkNode {
+                collaborator: collaborator.clone(),
+                x: center + radius * angle.cos(),
+                y: center + radius * angle.sin(),
+                weight: f64::from(collaborator.shared) / f64::from(strongest),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collaborator(bioguide_id: &str, shared: u32) -> Collaborator {
+        Collaborator {
+            bioguide_id: bioguide_id.to_string(),
+            name: None,
+            party: Some("R".to_string()),
+            shared,
+        }
+    }
+
+    #[test]
+    fn test_network_layout() {
+        let nodes = network_layout(
+            &[
+                collaborator("A000001", 8),
+                collaborator("B000002", 4),
+                collaborator("C000003", 2),
+                collaborator("D000004", 2),
+            ],
+            400.0,
+        );
+
+        let position = |node: &NetworkNode| (node.x.round(), node.y.round());
+        assert_eq!(position(&nodes[0]), (200.0, 48.0));
+        assert_eq!(position(&nodes[1]), (352.0, 200.0));
+        assert_eq!(position(&nodes[2]), (200.0, 352.0));
+        assert_eq!(nodes[0].weight, 1.0);
+        assert_eq!(nodes[3].weight, 0.25);
+        assert_eq!(nodes[3].collaborator.label(), "D000004");
+        assert!(network_layout(&[], 400.0).is_empty());
+    }
+
+    #[test]
+    fn test_bipartisanship_share() {
+        let bill = Bipartisanship {
+            cosponsors: 4,
+            across: 1,
+        };
+        assert_eq!(bill.share(), Some(0.25));
+        assert_eq!(Bipartisanship::default().share(), None);
+        assert_eq!(party_color(Some("Democratic")), party_color(Some("D")));
+    }
+}
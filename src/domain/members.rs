@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::domain::bills::{BillId, LatestAction, ResourceRef};
+
+/// Table of synced members, keyed by bioguide id like member:⟨S000033⟩.
+pub const MEMBER_TABLE: &str = "member";
+
+/// Path of a member's page.
+pub fn member_url(bioguide_id: &str) -> String {
+    format!("/members/{bioguide_id}")
+}
+
+pub fn member_thing(bioguide_id: &str) -> Thing {
+    Thing::from((MEMBER_TABLE, bioguide_id))
+}
+
+/// Official portrait of a member.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Depiction {
+    pub image_url: String,
+    pub attribution: Option<String>,
+}
+
+/// Chamber and years of a term as summarized in the member list.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TermSpan {
+    pub chamber: String,
+    pub start_year: Option<u32>,
+    pub end_year: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TermSpans {
+    #[serde(default)]
+    pub item: Vec<TermSpan>,
+}
+
+/// A member as listed by the Congress.gov `/member` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Member {
+    pub bioguide_id: String,
+    /// Last name first, e.g. "Leahy, Patrick J.".
+    pub name: String,
+    pub party_name: Option<String>,
+    pub state: Option<String>,
+    pub district: Option<u32>,
+    pub depiction: Option<Depiction>,
+    #[serde(default)]
+    pub terms: TermSpans,
+    pub update_date: Option<String>,
+}
+
+impl Member {
+    /// Chamber of the latest term.
+    pub fn chamber(&self) -> Option<&str> {
+        self.terms
+            .item
+            .iter()
+            .max_by_key(|term| term.start_year)
+            .map(|term| term.chamber.as_str())
+    }
+
+    /// Whether a term is still running.
+    pub fn is_current(&self) -> bool {
+        self.terms.item.iter().any(|term| term.end_year.is_none())
+    }
+}
+
+/// Filters of the member list at `/members?state=&chamber=`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct MemberListQuery {
+    /// State name, e.g. "Vermont".
+    pub state: Option<String>,
+    /// `house` or `senate`.
+    pub chamber: Option<String>,
+}
+
+impl MemberListQuery {
+    /// Reads the filters with `get`, ignoring empty values.
+    pub fn from_params<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let get = |key| {
+            get(key)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        Self {
+            state: get("state"),
+            chamber: get("chamber").map(|chamber| chamber.to_lowercase()),
+        }
+    }
+
+    pub fn matches(&self, member: &Member) -> bool {
+        let state = self.state.as_ref().is_none_or(|state| {
+            member
+                .state
+                .as_ref()
+                .is_some_and(|member_state| member_state.eq_ignore_ascii_case(state))
+        });
+        let chamber = self.chamber.as_ref().is_none_or(|chamber| {
+            member.chamber().is_some_and(|member_chamber| {
+                member_chamber.to_lowercase().starts_with(chamber.as_str())
+            })
+        });
+        state && chamber
+    }
+}
+
+/// One Congress served in one chamber.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Term {
+    pub chamber: String,
+    pub congress: Option<u32>,
+    /// "Representative", "Senator", "Delegate" or "Resident Commissioner".
+    pub member_type: Option<String>,
+    pub start_year: Option<u32>,
+    pub end_year: Option<u32>,
+    pub state_code: Option<String>,
+    pub state_name: Option<String>,
+    pub district: Option<u32>,
+}
+
+impl Term {
+    /// "2019–2021", or "2023–" while the term lasts.
+    pub fn years(&self) -> String {
+        let year = |year: Option<u32>| year.map(|year| year.to_string()).unwrap_or_default();
+        format!("{}–{}", year(self.start_year), year(self.end_year))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PartyAffiliation {
+    pub party_name: String,
+    pub party_abbreviation: Option<String>,
+    pub start_year: Option<u32>,
+    pub end_year: Option<u32>,
+}
+
+/// A member from the Congress.gov `/member/{bioguideId}` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberDetail {
+    pub bioguide_id: String,
+    pub first_name: String,
+    pub last_name: String,
+    /// First name first, e.g. "Patrick J. Leahy".
+    pub direct_order_name: Option<String>,
+    pub honorific_name: Option<String>,
+    pub birth_year: Option<String>,
+    pub state: Option<String>,
+    pub district: Option<u32>,
+    pub current_member: Option<bool>,
+    pub depiction: Option<Depiction>,
+    #[serde(default)]
+    pub party_history: Vec<PartyAffiliation>,
+    #[serde(default)]
+    pub terms: Vec<Term>,
+    pub sponsored_legislation: Option<ResourceRef>,
+    pub cosponsored_legislation: Option<ResourceRef>,
+    pub official_website_url: Option<String>,
+    pub update_date: Option<String>,
+}
+
+impl MemberDetail {
+    pub fn name(&self) -> String {
+        self.direct_order_name
+            .clone()
+            .unwrap_or_else(|| format!("{} {}", self.first_name, self.last_name))
+    }
+
+    /// The party the member belongs to now, or last belonged to.
+    pub fn party(&self) -> Option<&PartyAffiliation> {
+        self.party_history
+            .iter()
+            .max_by_key(|affiliation| affiliation.start_year)
+    }
+
+    /// Terms, most recent first.
+    pub fn terms_by_recency(&self) -> Vec<&Term> {
+        let mut terms: Vec<&Term> = self.terms.iter().collect();
+        terms.sort_by_key(|term| std::cmp::Reverse((term.congress, term.start_year)));
+        terms
+    }
+}
+
+impl From<&MemberDetail> for Member {
+    fn from(detail: &MemberDetail) -> Self {
+        let latest = detail.terms_by_recency().first().copied();
+        Self {
+            bioguide_id: detail.bioguide_id.clone(),
+            name: format!("{}, {}", detail.last_name, detail.first_name),
+            party_name: detail.party().map(|party| party.party_name.clone()),
+            state: detail.state.clone(),
+            district: latest.and_then(|term| term.district).or(detail.district),
+            depiction: detail.depiction.clone(),
+            terms: TermSpans {
+                item: detail
+                    .terms
+                    .iter()
+                    .map(|term| TermSpan {
+                        chamber: term.chamber.clone(),
+                        start_year: term.start_year,
+                        end_year: term.end_year,
+                    })
+                    .collect(),
+            },
+            update_date: detail.update_date.clone(),
+        }
+    }
+}
+
+/// A bill or amendment from the `/member/{bioguideId}/sponsored-legislation` and
+/// `/cosponsored-legislation` endpoints. Amendments have an `amendment_number` instead of a bill type and number.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberLegislation {
+    pub congress: Option<u32>,
+    #[serde(rename = "type")]
+    pub bill_type: Option<String>,
+    pub number: Option<String>,
+    pub amendment_number: Option<String>,
+    pub title: Option<String>,
+    pub introduced_date: Option<String>,
+    pub latest_action: Option<LatestAction>,
+}
+
+impl MemberLegislation {
+    /// `None` for amendments.
+    pub fn id(&self) -> Option<BillId> {
+        Some(BillId::new(
+            self.congress?,
+            self.bill_type.as_deref()?.parse().ok()?,
+            self.number.as_deref()?.parse().ok()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::bills::BillType;
+
+    #[test]
+    fn test_member_detail() {
+        let member: MemberDetail = serde_json::from_str(
+            r#"{
+                "bioguideId": "S000033",
+                "firstName": "Bernard",
+                "lastName": "Sanders",
+                "directOrderName": "Bernard Sanders",
+                "state": "Vermont",
+                "partyHistory": [
+                    { "partyName": "Independent", "partyAbbreviation": "I", "startYear": 1991 }
+                ],
+                "terms": [
+                    { "chamber": "House of Representatives", "congress": 102, "startYear": 1991, "endYear": 1993, "district": 0 },
+                    { "chamber": "Senate", "congress": 118, "startYear": 2023, "memberType": "Senator" }
+                ],
+                "sponsoredLegislation": { "count": 1040, "url": "https://api.congress.gov/v3/member/S000033/sponsored-legislation" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(member.name(), "Bernard Sanders");
+        assert_eq!(
+            member.party().map(|party| party.party_name.as_str()),
+            Some("Independent")
+        );
+        let terms = member.terms_by_recency();
+        assert_eq!(terms[0].chamber, "Senate");
+        assert_eq!(terms[0].years(), "2023–");
+        assert_eq!(terms[1].years(), "1991–1993");
+    }
+
+    #[test]
+    fn test_member_list_query() {
+        let member: Member = serde_json::from_str(
+            r#"{
+                "bioguideId": "S000033",
+                "name": "Sanders, Bernard",
+                "state": "Vermont",
+                "terms": { "item": [
+                    { "chamber": "House of Representatives", "startYear": 1991, "endYear": 2007 },
+                    { "chamber": "Senate", "startYear": 2007 }
+                ] }
+            }"#,
+        )
+        .unwrap();
+        assert!(member.is_current());
+
+        let query = |state, chamber| {
+            MemberListQuery::from_params(|key| match key {
+                "state" => Some(state),
+                "chamber" => Some(chamber),
+                _ => None,
+            })
+        };
+        assert!(query("", "").matches(&member));
+        assert!(query("vermont", "Senate").matches(&member));
+        assert!(!query("Vermont", "house").matches(&member));
+        assert!(!query("Maine", "").matches(&member));
+    }
+
+    #[test]
+    fn test_member_legislation_id() {
+        let legislation: Vec<MemberLegislation> = serde_json::from_str(
+            r#"[
+                { "congress": 118, "type": "S", "number": "1234", "title": "A bill" },
+                { "congress": 118, "amendmentNumber": "5031", "type": null, "number": null }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            legislation[0].id(),
+            Some(BillId::new(118, BillType::S, 1234))
+        );
+        assert_eq!(legislation[1].id(), None);
+    }
+}
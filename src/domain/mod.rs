@@ -1 +1,9 @@
+pub mod api_key;
 pub mod bills;
+pub mod committees;
+pub mod feeds;
+pub mod graph;
+pub mod members;
+pub mod search;
+pub mod sync;
+pub mod text_diff;
@@ -0,0 +1,212 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::bills::{BillId, BillType, LegislativeStage};
+use crate::domain::search::query::{QueryError, SearchQuery};
+
+pub mod query;
+
+/// Markers `search::highlight` wraps matched terms in. They are private use characters, so they
+/// can't clash with text from Congress.gov and results never have to be rendered as HTML.
+pub const MARK_START: char = '\u{E000}';
+pub const MARK_END: char = '\u{E001}';
+
+/// Parameters of the `/search` page: the search box `q`, written in the [`SearchQuery`] syntax,
+/// and the filter selects, e.g. `?q=sponsor:smith+clean+energy&congress=118&type=hr&stage=reported`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchParams {
+    pub q: String,
+    pub congress: Option<u32>,
+    pub bill_type: Option<BillType>,
+    pub stage: Option<LegislativeStage>,
+}
+
+impl SearchParams {
+    /// Reads the query parameters returned by `get`, ignoring selects that don't parse.
+    pub fn from_params<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let get = |key| get(key).map(str::trim).filter(|value| !value.is_empty());
+        Self {
+            q: get("q").unwrap_or_default().to_string(),
+            congress: get("congress").and_then(|congress| congress.parse().ok()),
+            bill_type: get("type").and_then(|bill_type| bill_type.parse().ok()),
+            stage: get("stage").and_then(|stage| stage.parse().ok()),
+        }
+    }
+
+    /// Parses the search box. Filters typed there win over the selects.
+    pub fn query(&self) -> Result<SearchQuery, Vec<QueryError>> {
+        let mut query: SearchQuery = self.q.parse()?;
+        query.congress = query.congress.or(self.congress);
+        query.bill_type = query.bill_type.or(self.bill_type);
+        query.stage = query.stage.or(self.stage);
+        Ok(query)
+    }
+}
+
+/// A stored bill matching a search, with matched terms marked in `title` and `summary`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SearchHit {
+    pub congress: u32,
+    #[serde(rename = "type")]
+    pub bill_type: BillType,
+    pub number: String,
+    pub title: String,
+    pub summary: Option<String>,
+    pub stage: Option<LegislativeStage>,
+    /// Sum of the BM25 scores of the matched fields.
+    pub score: f64,
+}
+
+impl SearchHit {
+    pub fn id(&self) -> Option<BillId> {
+        let number = self.number.parse().ok()?;
+        Some(BillId::new(self.congress, self.bill_type, number))
+    }
+}
+
+/// A run of highlighted text, either matched or not.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    pub text: String,
+    pub matched: bool,
+}
+
+/// Splits text marked with [`MARK_START`] and [`MARK_END`] into fragments.
+pub fn fragments(text: &str) -> Vec<Fragment> {
+    let mut fragments = Vec::new();
+    let mut current = String::new();
+    let mut matched = false;
+    for c in text.chars() {
+        if c != MARK_START && c != MARK_END {
+            current.push(c);
+            continue;
+        }
+        if !current.is_empty() {
+            fragments.push(Fragment {
+                text: std::mem::take(&mut current),
+                matched,
+            });
+        }
+        matched = c == MARK_START;
+    }
+    if !current.is_empty() {
+        fragments.push(Fragment {
+            text: current,
+            matched,
+        });
+    }
+    fragments
+}
+
+/// About `radius` characters before the first match and twice that after it, with ellipses where cut.
+pub fn snippet(text: &str, radius: usize) -> Vec<Fragment> {
+    let chars: Vec<char> = text.chars().collect();
+    let first = chars.iter().position(|c| *c == MARK_START).unwrap_or(0);
+    let start = first.saturating_sub(radius);
+    let end = (first + 2 * radius).min(chars.len());
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(&chars[start..end]);
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    fragments(&snippet)
+}
+
+/// Summaries come as HTML, the index only gets their text.
+pub fn plain_text(html: &str) -> String {
+    static TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]+>").unwrap());
+    static SPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+
+    let text = TAG.replace_all(html, " ");
+    let text = htmlize::unescape(text.as_ref());
+    SPACE.replace_all(text.trim(), " ").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marked(text: &str) -> String {
+        text.replace('[', &MARK_START.to_string())
+            .replace(']', &MARK_END.to_string())
+    }
+
+    #[test]
+    fn test_search_params() {
+        let params = [
+            ("q", " clean energy type:s "),
+            ("type", "hr"),
+            ("stage", "reported"),
+            ("congress", ""),
+        ];
+        let get = |key: &str| {
+            params
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| *value)
+        };
+        let params = SearchParams::from_params(get);
+        assert_eq!(
+            params,
+            SearchParams {
+                q: "clean energy type:s".to_string(),
+                congress: None,
+                bill_type: Some(BillType::HR),
+                stage: Some(LegislativeStage::Reported),
+            }
+        );
+        assert_eq!(
+            params.query(),
+            Ok(SearchQuery {
+                text: "clean energy".to_string(),
+                bill_type: Some(BillType::S),
+                stage: Some(LegislativeStage::Reported),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_fragments() {
+        let fragments = fragments(&marked("Promoting [clean] [energy] jobs"));
+        let texts: Vec<(&str, bool)> = fragments
+            .iter()
+            .map(|fragment| (fragment.text.as_str(), fragment.matched))
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                ("Promoting ", false),
+                ("clean", true),
+                (" ", false),
+                ("energy", true),
+                (" jobs", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text = marked(&format!("{} [energy] {}", "a".repeat(100), "b".repeat(100)));
+        let fragments = snippet(&text, 10);
+        assert!(fragments[0].text.starts_with('…'));
+        assert_eq!(fragments[1].text, "energy");
+        assert!(fragments.last().unwrap().text.ends_with('…'));
+
+        let fragments = snippet("no match here", 10);
+        assert_eq!(fragments[0].text, "no match here");
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(
+            plain_text("<p><strong>Clean Energy Act</strong></p>\n<p>This bill &amp; that.</p>"),
+            "Clean Energy Act This bill & that."
+        );
+    }
+}
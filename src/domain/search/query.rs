@@ -0,0 +1,344 @@
+use std::ops::Range;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::bills::{
+    BillTextVersionAbbreviation, BillType, BillTypeError, LegislativeStage,
+};
+
+/// A parsed search box query. Words and quoted phrases are matched against titles, summaries
+/// and subjects, `key:value` filters narrow the results, e.g.
+/// `type:hr congress:118 sponsor:"Smith" stage:reported "clean energy"`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchQuery {
+    /// Words and phrases to match, empty to only filter.
+    pub text: String,
+    /// Quoted phrases of several words, lowercased. Besides matching their words like `text`
+    /// does, a bill's title, summary or subjects must contain each of them as written.
+    pub phrases: Vec<String>,
+    pub congress: Option<u32>,
+    pub bill_type: Option<BillType>,
+    /// Part of a sponsor's name, matched ignoring case.
+    pub sponsor: Option<String>,
+    pub stage: Option<LegislativeStage>,
+    /// A text version the bill has, e.g. `version:enr`.
+    pub version: Option<BillTextVersionAbbreviation>,
+}
+
+/// Filter keys, in the order they're suggested in errors.
+pub const FILTERS: [&str; 5] = ["type", "congress", "sponsor", "stage", "version"];
+
+/// What is wrong with a query and where.
+#[derive(thiserror::Error, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct QueryError {
+    pub message: String,
+    /// Byte range of the offending part of the input.
+    pub span: Range<usize>,
+}
+
+impl QueryError {
+    fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+/// Parses the whole input and reports every error found, not just the first.
+impl FromStr for SearchQuery {
+    type Err = Vec<QueryError>;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut query = SearchQuery::default();
+        let mut text = Vec::new();
+        let mut errors = Vec::new();
+        let mut seen = Vec::new();
+
+        let mut pos = 0;
+        while let Some(c) = input[pos..].chars().next() {
+            if c.is_whitespace() {
+                pos += c.len_utf8();
+                continue;
+            }
+            if c == '"' {
+                match quoted(input, pos) {
+                    Ok((phrase, end)) => {
+                        let words: Vec<_> = phrase.split_whitespace().collect();
+                        if words.len() > 1 {
+                            query.phrases.push(words.join(" ").to_lowercase());
+                        }
+                        text.push(phrase);
+                        pos = end;
+                        continue;
+                    }
+                    Err(error) => {
+                        errors.push(error);
+                        break;
+                    }
+                }
+            }
+
+            let word_end = input[pos..]
+                .find(char::is_whitespace)
+                .map_or(input.len(), |end| pos + end);
+            let word = &input[pos..word_end];
+            let Some((key, value)) = word.split_once(':').filter(|(key, _)| !key.is_empty()) else {
+                text.push(word);
+                pos = word_end;
+                continue;
+            };
+
+            let value_start = pos + key.len() + 1;
+            let (value, end) = if value.starts_with('"') {
+                match quoted(input, value_start) {
+                    Ok(quoted) => quoted,
+                    Err(error) => {
+                        errors.push(error);
+                        break;
+                    }
+                }
+            } else {
+                (value, word_end)
+            };
+            let name = key.to_lowercase();
+            if seen.contains(&name) {
+                errors.push(QueryError::new(
+                    format!("`{key}:` is given twice"),
+                    pos..end,
+                ));
+            } else if let Err(error) = query.apply(key, value, pos..end, value_start..end) {
+                errors.push(error);
+            }
+            seen.push(name);
+            pos = end;
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        query.text = text.join(" ");
+        Ok(query)
+    }
+}
+
+/// The text between the quote at `start` and the next one, and where the closing quote ends.
+fn quoted(input: &str, start: usize) -> Result<(&str, usize), QueryError> {
+    let inner = start + 1;
+    match input[inner..].find('"') {
+        Some(length) => Ok((input[inner..inner + length].trim(), inner + length + 1)),
+        None => Err(QueryError::new("Missing closing quote", start..input.len())),
+    }
+}
+
+/// Sets a filter, reporting a bad value at `span`.
+fn set<T>(
+    field: &mut Option<T>,
+    value: Result<T, String>,
+    span: Range<usize>,
+) -> Result<(), QueryError> {
+    *field = Some(value.map_err(|message| QueryError::new(message, span))?);
+    Ok(())
+}
+
+impl SearchQuery {
+    fn apply(
+        &mut self,
+        key: &str,
+        value: &str,
+        span: Range<usize>,
+        value_span: Range<usize>,
+    ) -> Result<(), QueryError> {
+        let value = value.trim();
+        if value.is_empty() && FILTERS.contains(&key.to_lowercase().as_str()) {
+            return Err(QueryError::new(format!("`{key}:` needs a value"), span));
+        }
+        match key.to_lowercase().as_str() {
+            "type" => set(
+                &mut self.bill_type,
+                value
+                    .parse()
+                    .map_err(|error: BillTypeError| error.to_string()),
+                value_span,
+            ),
+            "congress" => set(
+                &mut self.congress,
+                value
+                    .parse()
+                    .ok()
+                    .filter(|congress| *congress > 0)
+                    .ok_or_else(|| format!("'{value}' is not a Congress number")),
+                value_span,
+            ),
+            "sponsor" => set(&mut self.sponsor, Ok(value.to_string()), value_span),
+            "stage" => set(
+                &mut self.stage,
+                value.parse().map_err(|_| {
+                    let stages: Vec<_> = LegislativeStage::ALL
+                        .iter()
+                        .map(|stage| stage.code())
+                        .collect();
+                    format!("'{value}' is not a stage, use one of {}", stages.join(", "))
+                }),
+                value_span,
+            ),
+            "version" => set(
+                &mut self.version,
+                BillTextVersionAbbreviation::from_code(value)
+                    .ok_or_else(|| format!("'{value}' is not a text version code")),
+                value_span,
+            ),
+            _ => Err(QueryError::new(
+                format!("Unknown filter `{key}:`, use {}", FILTERS.join(", ")),
+                span.start..span.start + key.len() + 1,
+            )),
+        }
+    }
+
+    /// Neither text nor filters, matches nothing.
+    pub fn is_empty(&self) -> bool {
+        self == &SearchQuery::default()
+    }
+
+    /// The `SELECT` for this query. Values are never inlined, the statement expects `$text`,
+    /// `$phrases`, `$congress`, `$type`, `$sponsor` (lowercased), `$stage`, `$version`,
+    /// `$mark_start`, `$mark_end` and `$limit`. Text searches are ranked by the sum of the BM25
+    /// scores of the matched fields, filter-only queries list the most recently updated bills first.
+    pub fn to_surql(&self) -> String {
+        let mut conditions = Vec::new();
+        if !self.text.is_empty() {
+            conditions
+                .push("(title @1@ $text OR summary @2@ $text OR subjects @3@ $text)".to_string());
+        }
+        // The full-text index only knows single words, so phrases are checked on the fields.
+        conditions.extend((0..self.phrases.len()).map(|index| {
+            format!(
+                "(string::contains(string::lowercase(title), $phrases[{index}]) \
+                 OR string::contains(string::lowercase(summary ?? ''), $phrases[{index}]) \
+                 OR string::contains(string::lowercase(array::join(subjects ?? [], ' ')), $phrases[{index}]))"
+            )
+        }));
+        if self.congress.is_some() {
+            conditions.push("congress = $congress".to_string());
+        }
+        if self.bill_type.is_some() {
+            conditions.push("type = $type".to_string());
+        }
+        if self.sponsor.is_some() {
+            conditions.push(
+                "string::contains(string::lowercase(array::join(detail.sponsors.fullName ?? [], ' ')), $sponsor)"
+                    .to_string(),
+            );
+        }
+        if self.stage.is_some() {
+            conditions.push("stage = $stage".to_string());
+        }
+        if self.version.is_some() {
+            conditions.push("$version INSIDE versions".to_string());
+        }
+        if conditions.is_empty() {
+            conditions.push("false".to_string());
+        }
+
+        let (fields, order) = if self.text.is_empty() {
+            ("title, summary, 0.0 AS score", "updateDate DESC")
+        } else {
+            (
+                "(search::highlight($mark_start, $mark_end, 1) ?? title) AS title, \
+                 (search::highlight($mark_start, $mark_end, 2) ?? summary) AS summary, \
+                 (search::score(1) ?? 0) + (search::score(2) ?? 0) + (search::score(3) ?? 0) AS score",
+                "score DESC",
+            )
+        };
+        format!(
+            "SELECT congress, type, number, stage, {fields} FROM bill WHERE {} ORDER BY {order} LIMIT $limit",
+            conditions.join(" AND ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let query: SearchQuery =
+            r#"type:hr congress:118 sponsor:"Smith" stage:reported "clean energy" jobs"#
+                .parse()
+                .unwrap();
+        assert_eq!(
+            query,
+            SearchQuery {
+                text: "clean energy jobs".to_string(),
+                phrases: vec!["clean energy".to_string()],
+                congress: Some(118),
+                bill_type: Some(BillType::HR),
+                sponsor: Some("Smith".to_string()),
+                stage: Some(LegislativeStage::Reported),
+                version: None,
+            }
+        );
+
+        let query: SearchQuery = "Version:ENR S.J.Res. sponsor:\"Mary  Smith\""
+            .parse()
+            .unwrap();
+        assert_eq!(query.version, Some(BillTextVersionAbbreviation::ENR));
+        assert_eq!(query.sponsor.as_deref(), Some("Mary  Smith"));
+        assert_eq!(query.text, "S.J.Res.");
+        assert!(query.phrases.is_empty());
+
+        // A quoted single word is just a word.
+        let query: SearchQuery = r#""Solar"  " Clean   Water ""#.parse().unwrap();
+        assert_eq!(query.text, "Solar Clean   Water");
+        assert_eq!(query.phrases, ["clean water"]);
+
+        assert!("  ".parse::<SearchQuery>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_query_errors() {
+        let input = r#"type:hx congress:0 color:red stage: type:s "clean"#;
+        let errors = input.parse::<SearchQuery>().unwrap_err();
+        let found: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|error| (&input[error.span.clone()], error.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("hx", "'hx' is not a bill type"),
+                ("0", "'0' is not a Congress number"),
+                (
+                    "color:",
+                    "Unknown filter `color:`, use type, congress, sponsor, stage, version"
+                ),
+                ("stage:", "`stage:` needs a value"),
+                ("type:s", "`type:` is given twice"),
+                ("\"clean", "Missing closing quote"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_surql() {
+        let query: SearchQuery = "congress:118 version:enr".parse().unwrap();
+        assert_eq!(
+            query.to_surql(),
+            "SELECT congress, type, number, stage, title, summary, 0.0 AS score FROM bill \
+             WHERE congress = $congress AND $version INSIDE versions \
+             ORDER BY updateDate DESC LIMIT $limit"
+        );
+
+        let query: SearchQuery = r#"sponsor:smith "clean energy""#.parse().unwrap();
+        let surql = query.to_surql();
+        assert!(surql.contains(
+            "WHERE (title @1@ $text OR summary @2@ $text OR subjects @3@ $text) \
+             AND (string::contains(string::lowercase(title), $phrases[0]) OR "
+        ));
+        assert!(surql.ends_with("ORDER BY score DESC LIMIT $limit"));
+        assert!(!surql.contains("smith"));
+    }
+}
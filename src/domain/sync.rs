@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Datetime;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncState {
+    Running,
+    Ok,
+    Failed,
+}
+
+impl Display for SyncState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SyncState::Running => write!(f, "running"),
+            SyncState::Ok => write!(f, "ok"),
+            SyncState::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// Last run of a background sync, stored in the `sync_state` table.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SyncStatus {
+    pub state: SyncState,
+    /// Records updated on Congress.gov after this were not synced yet.
+    pub cursor: Option<Datetime>,
+    pub started_at: Datetime,
+    pub finished_at: Option<Datetime>,
+    /// Records written by the last run.
+    pub synced: u32,
+    pub error: Option<String>,
+}
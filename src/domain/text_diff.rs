@@ -0,0 +1,279 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::bills::BillTextVersionAbbreviation;
+
+/// Start of a section in GPO bill text, e.g. `SEC. 2. FINDINGS.` or `SECTION 1. SHORT TITLE.`.
+static SECTION_START: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:SEC\.|SECTION)[ \t]+(?<number>\d+[A-Za-z]*)\.(?<title>[^\n]*)")
+        .unwrap()
+});
+
+/// A section of a bill text, keyed by its number so versions can be lined up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub key: String,
+    pub heading: String,
+    pub body: String,
+}
+
+/// Splits bill text at section headings. Text before the first heading becomes a `preamble` section.
+///
+/// Section numbers repeat when a bill quotes the law it amends, so repeated keys get a `-2`, `-3`, ... suffix.
+pub fn split_sections(text: &str) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+
+    let starts: Vec<_> = SECTION_START.captures_iter(text).collect();
+    let first = starts
+        .first()
+        .map(|caps| caps.get(0).unwrap().start())
+        .unwrap_or(text.len());
+    if !text[..first].trim().is_empty() {
+        sections.push(Section {
+            key: "preamble".to_string(),
+            heading: "Preamble".to_string(),
+            body: text[..first].trim().to_string(),
+        });
+    }
+
+    for (i, caps) in starts.iter().enumerate() {
+        let whole = caps.get(0).unwrap();
+        let end = starts
+            .get(i + 1)
+            .map(|next| next.get(0).unwrap().start())
+            .unwrap_or(text.len());
+
+        let number = caps["number"].to_lowercase();
+        seen.push(number.clone());
+        let occurrence = seen.iter().filter(|key| **key == number).count();
+        let key = if occurrence > 1 {
+            format!("{number}-{occurrence}")
+        } else {
+            number
+        };
+
+        sections.push(Section {
+            key,
+            heading: format!("SEC. {}.{}", &caps["number"], &caps["title"])
+                .trim()
+                .to_string(),
+            body: text[whole.start()..end].trim().to_string(),
+        });
+    }
+
+    sections
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DiffSpan {
+    pub kind: ChangeKind,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionStatus {
+    Added,
+    Removed,
+    Modified,
+    Unchanged,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SectionDiff {
+    pub key: String,
+    pub heading: String,
+    pub status: SectionStatus,
+    pub spans: Vec<DiffSpan>,
+}
+
+impl SectionDiff {
+    /// Fragment id used to link to the section from the summary.
+    pub fn anchor(&self) -> String {
+        format!("sec-{}", self.key)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub modified: usize,
+    pub unchanged: usize,
+}
+
+/// Section and word level comparison of two versions of the same bill.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BillTextDiff {
+    pub from: BillTextVersionAbbreviation,
+    pub to: BillTextVersionAbbreviation,
+    pub sections: Vec<SectionDiff>,
+    pub summary: DiffSummary,
+}
+
+impl BillTextDiff {
+    #[cfg(feature = "ssr")]
+    pub fn new(
+        from: BillTextVersionAbbreviation,
+        to: BillTextVersionAbbreviation,
+        from_text: &str,
+        to_text: &str,
+    ) -> Self {
+        let sections = diff_sections(from_text, to_text);
+
+        let mut summary = DiffSummary::default();
+        for section in &sections {
+            match section.status {
+                SectionStatus::Added => summary.added += 1,
+                SectionStatus::Removed => summary.removed += 1,
+                SectionStatus::Modified => summary.modified += 1,
+                SectionStatus::Unchanged => summary.unchanged += 1,
+            }
+        }
+
+        Self {
+            from,
+            to,
+            sections,
+            summary,
+        }
+    }
+}
+
+/// Lines up the sections of both texts in the order of the newer one, removed sections go last.
+#[cfg(feature = "ssr")]
+pub fn diff_sections(from_text: &str, to_text: &str) -> Vec<SectionDiff> {
+    let old = split_sections(from_text);
+    let new = split_sections(to_text);
+
+    let mut diffs: Vec<SectionDiff> = new
+        .iter()
+        .map(|section| match old.iter().find(|o| o.key == section.key) {
+            Some(previous) => {
+                let spans = diff_words(&previous.body, &section.body);
+                let status = if spans.iter().all(|span| span.kind == ChangeKind::Equal) {
+                    SectionStatus::Unchanged
+                } else {
+                    SectionStatus::Modified
+                };
+                SectionDiff {
+                    key: section.key.clone(),
+                    heading: section.heading.clone(),
+                    status,
+                    spans,
+                }
+            }
+            None => SectionDiff {
+                key: section.key.clone(),
+                heading: section.heading.clone(),
+                status: SectionStatus::Added,
+                spans: vec![DiffSpan {
+                    kind: ChangeKind::Insert,
+                    text: section.body.clone(),
+                }],
+            },
+        })
+        .collect();
+
+    diffs.extend(
+        old.iter()
+            .filter(|section| !new.iter().any(|n| n.key == section.key))
+            .map(|section| SectionDiff {
+                key: section.key.clone(),
+                heading: section.heading.clone(),
+                status: SectionStatus::Removed,
+                spans: vec![DiffSpan {
+                    kind: ChangeKind::Delete,
+                    text: section.body.clone(),
+                }],
+            }),
+    );
+
+    diffs
+}
+
+/// Word level diff, merging consecutive words with the same change into one span.
+#[cfg(feature = "ssr")]
+pub fn diff_words(old: &str, new: &str) -> Vec<DiffSpan> {
+    use similar::{ChangeTag, TextDiff};
+
+    let mut spans: Vec<DiffSpan> = Vec::new();
+    for change in TextDiff::from_words(old, new).iter_all_changes() {
+        let kind = match change.tag() {
+            ChangeTag::Equal => ChangeKind::Equal,
+            ChangeTag::Insert => ChangeKind::Insert,
+            ChangeTag::Delete => ChangeKind::Delete,
+        };
+        match spans.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(change.value()),
+            _ => spans.push(DiffSpan {
+                kind,
+                text: change.value().to_string(),
+            }),
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTRODUCED: &str = "A BILL\nTo improve arts education.\n\nSECTION 1. SHORT TITLE.\n\nThis Act may be cited as the Arts Act.\n\nSEC. 2. GRANTS.\n\nThe Secretary may award grants.\n\nSEC. 3. REPORT.\n\nA report is due in 1 year.\n";
+    const REPORTED: &str = "A BILL\nTo improve arts education.\n\nSECTION 1. SHORT TITLE.\n\nThis Act may be cited as the Arts Act.\n\nSEC. 2. GRANTS.\n\nThe Secretary shall award grants.\n\nSEC. 4. FUNDING.\n\nSuch sums as necessary.\n";
+
+    #[test]
+    fn test_split_sections() {
+        let sections = split_sections(INTRODUCED);
+
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[0].key, "preamble");
+        assert_eq!(sections[1].key, "1");
+        assert_eq!(sections[2].heading, "SEC. 2. GRANTS.");
+        assert!(sections[3].body.ends_with("1 year."));
+    }
+
+    #[test]
+    fn test_repeated_section_numbers() {
+        let keys: Vec<_> = split_sections("SEC. 2. AMENDMENT.\nSEC. 2. QUOTED.\n")
+            .into_iter()
+            .map(|section| section.key)
+            .collect();
+
+        assert_eq!(keys, vec!["2", "2-2"]);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_diff_sections() {
+        let diff = BillTextDiff::new(
+            BillTextVersionAbbreviation::IH,
+            BillTextVersionAbbreviation::RH,
+            INTRODUCED,
+            REPORTED,
+        );
+
+        assert_eq!(diff.summary.unchanged, 2);
+        assert_eq!(diff.summary.modified, 1);
+        assert_eq!(diff.summary.added, 1);
+        assert_eq!(diff.summary.removed, 1);
+
+        let grants = diff.sections.iter().find(|s| s.key == "2").unwrap();
+        assert!(grants
+            .spans
+            .iter()
+            .any(|span| span.kind == ChangeKind::Delete && span.text == "may"));
+        assert!(grants
+            .spans
+            .iter()
+            .any(|span| span.kind == ChangeKind::Insert && span.text == "shall"));
+    }
+}
@@ -70,3 +70,11 @@ pub fn ErrorTemplate(
         />
     }
 }
+
+/// The 404 page, also for routes whose parameters don't parse, e.g. `/bills/abc/hr/1`.
+#[component]
+pub fn NotFound() -> impl IntoView {
+    let mut outside_errors = Errors::default();
+    outside_errors.insert_with_default_key(AppError::NotFound);
+    view! { <ErrorTemplate outside_errors/> }
+}
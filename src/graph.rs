@@ -0,0 +1,129 @@
+//! Queries over the sponsorship graph from `migrations/0008_sponsorship_edges.surql`, where
+//! [`crate::ingest::relate_sponsors`] keeps `member->sponsored->bill` and
+//! `member->cosponsored->bill` edges.
+
+use surrealdb::{Connection, Surreal};
+
+use crate::domain::bills::BillId;
+use crate::domain::graph::{Bipartisanship, Collaborator};
+use crate::domain::members::member_thing;
+
+/// Members who cosponsored the most bills that the given member sponsored or cosponsored, most
+/// shared bills first. Withdrawn cosponsorships don't count.
+pub async fn frequent_cosponsors<C: Connection>(
+    db: &Surreal<C>,
+    bioguide_id: &str,
+    limit: usize,
+) -> Result<Vec<Collaborator>, surrealdb::Error> {
+    db.query(
+        "LET $bills = array::union( \
+             (SELECT VALUE out FROM sponsored WHERE in = $member), \
+             (SELECT VALUE out FROM cosponsored WHERE in = $member AND withdrawn = NONE) \
+         ); \
+         SELECT meta::id(in) AS bioguideId, in.name AS name, in.partyName AS party, shared FROM ( \
+             SELECT in, count() AS shared FROM cosponsored \
+             WHERE out INSIDE $bills AND in != $member AND withdrawn = NONE GROUP BY in \
+         ) ORDER BY shared DESC LIMIT $limit;",
+    )
+    .bind(("member", member_thing(bioguide_id)))
+    .bind(("limit", limit))
+    .await?
+    .take(1)
+}
+
+/// How many current cosponsors of a bill are from another party than its sponsor, `None` until
+/// the bill's edges are stored.
+pub async fn bipartisanship<C: Connection>(
+    db: &Surreal<C>,
+    id: &BillId,
+) -> Result<Option<Bipartisanship>, surrealdb::Error> {
+    let mut response = db
+        .query(
+            "LET $party = (SELECT VALUE party FROM sponsored WHERE out = $bill)[0]; \
+             SELECT count() AS cosponsors, count(party != $party) AS across FROM cosponsored \
+             WHERE out = $bill AND withdrawn = NONE GROUP ALL; \
+             RETURN $party;",
+        )
+        .bind(("bill", id.thing()))
+        .await?;
+    let counts: Option<Bipartisanship> = response.take(1)?;
+    let party: Option<String> = response.take(2)?;
+    Ok(party.map(|_| counts.unwrap_or_default()))
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sponsorship_graph() {
+        use crate::domain::bills::{BillDetail, BillType, Cosponsor};
+        use crate::ingest::relate_sponsors;
+
+        let db = crate::migrations::test_db().await;
+
+        let sponsor = |bioguide_id: &str, party: &str| {
+            serde_json::json!({
+                "bioguideId": bioguide_id, "fullName": bioguide_id, "party": party, "state": "VT"
+            })
+        };
+        let cosponsor = |bioguide_id: &str, party: &str, withdrawn: Option<&str>| -> Cosponsor {
+            serde_json::from_value(serde_json::json!({
+                "bioguideId": bioguide_id, "fullName": bioguide_id, "party": party, "state": "VT",
+                "sponsorshipDate": "2023-02-01", "sponsorshipWithdrawnDate": withdrawn
+            }))
+            .unwrap()
+        };
+        for (number, sponsors, cosponsors) in [
+            (
+                1,
+                sponsor("A000001", "D"),
+                vec![
+                    cosponsor("B000002", "D", None),
+                    cosponsor("C000003", "R", None),
+                    cosponsor("D000004", "R", Some("2023-03-01")),
+                ],
+            ),
+            (
+                2,
+                sponsor("C000003", "R"),
+                vec![
+                    cosponsor("A000001", "D", None),
+                    cosponsor("B000002", "D", None),
+                ],
+            ),
+        ] {
+            let detail: BillDetail = serde_json::from_value(serde_json::json!({
+                "congress": 118, "number": number.to_string(), "title": "A bill", "type": "HR",
+                "introducedDate": "2023-01-10", "sponsors": [sponsors]
+            }))
+            .unwrap();
+            let id = BillId::new(118, BillType::HR, number);
+            relate_sponsors(&db, &id, &detail, &cosponsors)
+                .await
+                .unwrap();
+            // Storing the same edges again replaces them.
+            relate_sponsors(&db, &id, &detail, &cosponsors)
+                .await
+                .unwrap();
+        }
+
+        let collaborators = frequent_cosponsors(&db, "A000001", 10).await.unwrap();
+        let shared: Vec<(&str, u32)> = collaborators
+            .iter()
+            .map(|collaborator| (collaborator.bioguide_id.as_str(), collaborator.shared))
+            .collect();
+        assert_eq!(shared, [("B000002", 2), ("C000003", 1)]);
+
+        let bill = BillId::new(118, BillType::HR, 1);
+        assert_eq!(
+            bipartisanship(&db, &bill).await.unwrap(),
+            Some(Bipartisanship {
+                cosponsors: 2,
+                across: 1
+            })
+        );
+        let unknown = BillId::new(118, BillType::HR, 3);
+        assert_eq!(bipartisanship(&db, &unknown).await.unwrap(), None);
+    }
+}
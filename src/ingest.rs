@@ -0,0 +1,465 @@
+//! Background sync of Congress.gov bills into SurrealDB, so pages can render from local records
+//! when the API is slow or down.
+
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use leptos::logging::{log, warn};
+use serde::Serialize;
+use surrealdb::sql::{Datetime, Thing};
+use surrealdb::{Connection, Surreal};
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::config::IngestSettings;
+use crate::congress::{BillQuery, CongressClient, CongressError};
+use crate::domain::bills::{
+    BillDetail, BillId, BillListItem, BillSort, Cosponsor, LegislativeStage,
+};
+use crate::domain::members::{member_thing, Member, MemberDetail};
+use crate::domain::search::plain_text;
+use crate::domain::sync::{SyncState, SyncStatus};
+
+/// `sync_state` record of the bill sync.
+pub const BILLS: &str = "bills";
+
+/// `sync_state` record of the member sync.
+pub const MEMBERS: &str = "members";
+
+/// Members change rarely, so their list is synced once this many hours passed since a successful run.
+const MEMBER_SYNC_HOURS: i64 = 24;
+
+/// Bills indexed for search per run. Each one takes five API requests.
+const INDEX_BATCH: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum IngestError {
+    #[error("Database error: {0}")]
+    Db(#[from] surrealdb::Error),
+    #[error(transparent)]
+    Congress(#[from] CongressError),
+}
+
+/// Runs [`sync_bills`], [`sync_members`] when due and [`index_bills`] right away and then every
+/// `interval_secs`, logging the outcome of each run.
+pub fn spawn<C: Connection>(
+    db: Surreal<C>,
+    client: CongressClient,
+    settings: IngestSettings,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settings.interval_secs));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match sync_bills(&db, &client, &settings).await {
+                Ok(synced) => log!("bill sync: {synced} bill(s) updated"),
+                Err(error) => warn!("bill sync failed: {error}"),
+            }
+            match members_due(&db).await {
+                Ok(true) => match sync_members(&db, &client).await {
+                    Ok(synced) => log!("member sync: {synced} member(s) updated"),
+                    Err(error) => warn!("member sync failed: {error}"),
+                },
+                Ok(false) => {}
+                Err(error) => warn!("member sync status unavailable: {error}"),
+            }
+            match index_bills(&db, &client).await {
+                Ok(0) => {}
+                Ok(indexed) => log!("bill search: {indexed} bill(s) indexed"),
+                Err(error) => warn!("bill search indexing failed: {error}"),
+            }
+        }
+    })
+}
+
+/// The last run of the named sync, `None` before the first one.
+pub async fn status<C: Connection>(
+    db: &Surreal<C>,
+    name: &str,
+) -> Result<Option<SyncStatus>, surrealdb::Error> {
+    db.select(("sync_state", name)).await
+}
+
+/// Pulls bills updated since the stored cursor and upserts them into the `bill` table.
+///
+/// The cursor only moves forward when the whole run succeeds, so a failed run is retried
+/// from the same point next time.
+pub async fn sync_bills<C: Connection>(
+    db: &Surreal<C>,
+    client: &CongressClient,
+    settings: &IngestSettings,
+) -> Result<u32, IngestError> {
+    let started_at = Utc::now();
+    let cursor = status(db, BILLS).await?.and_then(|status| status.cursor);
+    let from = cursor
+        .as_ref()
+        .map(|cursor| cursor.0)
+        .unwrap_or_else(|| started_at - chrono::Duration::days(settings.lookback_days.into()));
+
+    let mut status = SyncStatus {
+        state: SyncState::Running,
+        cursor: cursor.clone(),
+        started_at: Datetime::from(started_at),
+        finished_at: None,
+        synced: 0,
+        error: None,
+    };
+    save_status(db, BILLS, &status).await?;
+
+    let result = sync_bills_between(db, client, from, started_at).await;
+    status.finished_at = Some(Datetime::from(Utc::now()));
+    match &result {
+        Ok(synced) => {
+            status.state = SyncState::Ok;
+            status.cursor = Some(Datetime::from(started_at));
+            status.synced = *synced;
+        }
+        Err(error) => {
+            status.state = SyncState::Failed;
+            status.error = Some(error.to_string());
+        }
+    }
+    save_status(db, BILLS, &status).await?;
+
+    result
+}
+
+async fn sync_bills_between<C: Connection>(
+    db: &Surreal<C>,
+    client: &CongressClient,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<u32, IngestError> {
+    let bills = client
+        .all_bills(&BillQuery {
+            from_date_time: Some(from.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            to_date_time: Some(to.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            sort: Some(BillSort::Oldest.api().to_string()),
+            ..Default::default()
+        })
+        .await?;
+
+    let mut synced = 0;
+    for bill in &bills {
+        match bill.id() {
+            Some(id) => {
+                store_bill(db, &id, bill, None).await?;
+                synced += 1;
+            }
+            None => warn!(
+                "bill sync: skipping {} {} with an invalid number",
+                bill.bill_type, bill.number
+            ),
+        }
+    }
+    Ok(synced)
+}
+
+/// Upserts a bill, keeping fields that are already stored such as `detail` unless a new one is given.
+pub async fn store_bill<C: Connection>(
+    db: &Surreal<C>,
+    id: &BillId,
+    bill: &BillListItem,
+    detail: Option<&BillDetail>,
+) -> Result<(), surrealdb::Error> {
+    let mut query = db
+        .query("UPDATE $id MERGE $bill RETURN NONE")
+        .query("UPDATE $id SET synced_at = time::now() RETURN NONE")
+        .bind(("id", id.thing()))
+        .bind(("bill", bill));
+    if let Some(detail) = detail {
+        query = query
+            .query("UPDATE $id SET detail = $detail RETURN NONE")
+            .bind(("detail", detail));
+    }
+    query.await?.check()?;
+    Ok(())
+}
+
+/// Upserts every current member of Congress into the `member` table.
+pub async fn sync_members<C: Connection>(
+    db: &Surreal<C>,
+    client: &CongressClient,
+) -> Result<u32, IngestError> {
+    let mut status = SyncStatus {
+        state: SyncState::Running,
+        cursor: None,
+        started_at: Datetime::from(Utc::now()),
+        finished_at: None,
+        synced: 0,
+        error: None,
+    };
+    save_status(db, MEMBERS, &status).await?;
+
+    let result = async {
+        let members = client.current_members().await?;
+        for member in &members {
+            store_member(db, member, None).await?;
+        }
+        Ok::<_, IngestError>(members.len() as u32)
+    }
+    .await;
+    status.finished_at = Some(Datetime::from(Utc::now()));
+    match &result {
+        Ok(synced) => {
+            status.state = SyncState::Ok;
+            status.synced = *synced;
+        }
+        Err(error) => {
+            status.state = SyncState::Failed;
+            status.error = Some(error.to_string());
+        }
+    }
+    save_status(db, MEMBERS, &status).await?;
+
+    result
+}
+
+/// Whether the last successful member sync is older than [`MEMBER_SYNC_HOURS`], or there was none.
+async fn members_due<C: Connection>(db: &Surreal<C>) -> Result<bool, surrealdb::Error> {
+    let finished_at = status(db, MEMBERS)
+        .await?
+        .filter(|status| status.state == SyncState::Ok)
+        .and_then(|status| status.finished_at);
+    Ok(finished_at.is_none_or(|finished_at| {
+        Utc::now() - finished_at.0 > chrono::Duration::hours(MEMBER_SYNC_HOURS)
+    }))
+}
+
+/// Upserts a member like [`store_bill`] does a bill, keeping a stored `detail` unless a new one is given.
+pub async fn store_member<C: Connection>(
+    db: &Surreal<C>,
+    member: &Member,
+    detail: Option<&MemberDetail>,
+) -> Result<(), surrealdb::Error> {
+    let mut query = db
+        .query("UPDATE $id MERGE $member RETURN NONE")
+        .query("UPDATE $id SET synced_at = time::now() RETURN NONE")
+        .bind(("id", member_thing(&member.bioguide_id)))
+        .bind(("member", member));
+    if let Some(detail) = detail {
+        query = query
+            .query("UPDATE $id SET detail = $detail RETURN NONE")
+            .bind(("detail", detail));
+    }
+    query.await?.check()?;
+    Ok(())
+}
+
+/// Indexes bills synced since they were last indexed, most recently synced first.
+///
+/// A bill that fails is logged and stays pending for the next run.
+pub async fn index_bills<C: Connection>(
+    db: &Surreal<C>,
+    client: &CongressClient,
+) -> Result<u32, IngestError> {
+    let pending: Vec<Thing> = db
+        .query(
+            "SELECT VALUE id FROM bill WHERE indexed_at = NONE OR indexed_at < synced_at \
+             ORDER BY synced_at DESC LIMIT $limit",
+        )
+        .bind(("limit", INDEX_BATCH))
+        .await?
+        .take(0)?;
+
+    let mut indexed = 0;
+    for thing in &pending {
+        let Ok(id) = BillId::try_from(thing) else {
+            warn!("bill search: skipping {thing}, not a bill id");
+            continue;
+        };
+        match index_bill(db, client, &id).await {
+            Ok(()) => indexed += 1,
+            Err(error) => warn!("bill search: indexing {id} failed: {error}"),
+        }
+    }
+    Ok(indexed)
+}
+
+/// Stores what search matches and filters on besides the title: the latest summary, the subjects,
+/// the detail with its sponsors, and the text versions with the stage they reached. Also relates
+/// the sponsors and cosponsors, see [`relate_sponsors`].
+pub async fn index_bill<C: Connection>(
+    db: &Surreal<C>,
+    client: &CongressClient,
+    id: &BillId,
+) -> Result<(), IngestError> {
+    let detail = client.bill(id).await?;
+    let summaries = client.bill_summaries(id).await?;
+    let subjects = client.bill_subjects(id).await?;
+    let versions = client.bill_text(id).await?;
+    let cosponsors = client.bill_cosponsors(id).await?;
+
+    let summary = summaries
+        .iter()
+        .max_by(|a, b| a.action_date.cmp(&b.action_date))
+        .map(|summary| plain_text(&summary.text));
+    let subjects: Vec<String> = subjects
+        .legislative_subjects
+        .into_iter()
+        .map(|subject| subject.name)
+        .chain(subjects.policy_area.map(|area| area.name))
+        .collect();
+    let abbreviations: Vec<_> = versions
+        .iter()
+        .filter_map(|version| version.abbreviation())
+        .collect();
+    let stage = LegislativeStage::reached(&abbreviations);
+    let versions: Vec<&str> = abbreviations
+        .iter()
+        .map(|abbreviation| abbreviation.code())
+        .collect();
+
+    db.query(
+        "UPDATE $id SET detail = $detail, summary = $summary, subjects = $subjects, \
+         stage = $stage, versions = $versions, indexed_at = time::now() RETURN NONE",
+    )
+    .bind(("id", id.thing()))
+    .bind(("detail", &detail))
+    .bind(("summary", summary))
+    .bind(("subjects", subjects))
+    .bind(("stage", stage))
+    .bind(("versions", versions))
+    .await?
+    .check()?;
+    relate_sponsors(db, id, &detail, &cosponsors).await?;
+    Ok(())
+}
+
+/// Replaces the `member->sponsored->bill` and `member->cosponsored->bill` edges of a bill.
+pub async fn relate_sponsors<C: Connection>(
+    db: &Surreal<C>,
+    id: &BillId,
+    detail: &BillDetail,
+    cosponsors: &[Cosponsor],
+) -> Result<(), surrealdb::Error> {
+    #[derive(Serialize)]
+    struct Edge<'a> {
+        member: Thing,
+        date: Option<&'a str>,
+        withdrawn: Option<&'a str>,
+        party: &'a str,
+    }
+
+    let sponsors: Vec<Edge> = detail
+        .sponsors
+        .iter()
+        .map(|sponsor| Edge {
+            member: member_thing(&sponsor.bioguide_id),
+            date: detail.introduced_date.as_deref(),
+            withdrawn: None,
+            party: &sponsor.party,
+        })
+        .collect();
+    let cosponsors: Vec<Edge> = cosponsors
+        .iter()
+        .map(|cosponsor| Edge {
+            member: member_thing(&cosponsor.bioguide_id),
+            date: Some(&cosponsor.sponsorship_date),
+            withdrawn: cosponsor.sponsorship_withdrawn_date.as_deref(),
+            party: &cosponsor.party,
+        })
+        .collect();
+
+    db.query(
+        "BEGIN TRANSACTION; \
+         DELETE sponsored WHERE out = $bill; \
+         DELETE cosponsored WHERE out = $bill; \
+         FOR $edge IN $sponsors { \
+             LET $member = $edge.member; \
+             RELATE $member->sponsored->$bill SET date = $edge.date, withdrawn = $edge.withdrawn, party = $edge.party; \
+         }; \
+         FOR $edge IN $cosponsors { \
+             LET $member = $edge.member; \
+             RELATE $member->cosponsored->$bill SET date = $edge.date, withdrawn = $edge.withdrawn, party = $edge.party; \
+         }; \
+         COMMIT TRANSACTION;",
+    )
+    .bind(("bill", id.thing()))
+    .bind(("sponsors", sponsors))
+    .bind(("cosponsors", cosponsors))
+    .await?
+    .check()?;
+    Ok(())
+}
+
+async fn save_status<C: Connection>(
+    db: &Surreal<C>,
+    name: &str,
+    status: &SyncStatus,
+) -> Result<(), surrealdb::Error> {
+    db.query("UPDATE type::thing('sync_state', $name) CONTENT $status RETURN NONE")
+        .bind(("name", name))
+        .bind(("status", status))
+        .await?
+        .check()?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_bill_and_status() {
+        use crate::domain::bills::BillType;
+
+        let db = crate::migrations::test_db().await;
+
+        let bill: BillListItem = serde_json::from_str(
+            r#"{ "congress": 118, "number": "7261", "title": "Arts Education Act", "type": "HR" }"#,
+        )
+        .unwrap();
+        let id = bill.id().unwrap();
+        store_bill(&db, &id, &bill, None).await.unwrap();
+        store_bill(&db, &id, &bill, None).await.unwrap();
+
+        let stored: Vec<BillListItem> = db.select(BillId::TABLE).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].bill_type, BillType::HR);
+
+        assert!(status(&db, BILLS).await.unwrap().is_none());
+        let started_at = Datetime::from(Utc::now());
+        save_status(
+            &db,
+            BILLS,
+            &SyncStatus {
+                state: SyncState::Ok,
+                cursor: Some(started_at.clone()),
+                started_at,
+                finished_at: None,
+                synced: 1,
+                error: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status(&db, BILLS).await.unwrap().map(|status| status.state),
+            Some(SyncState::Ok)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_member() {
+        use crate::domain::members::MEMBER_TABLE;
+
+        let db = crate::migrations::test_db().await;
+
+        let detail: MemberDetail = serde_json::from_str(
+            r#"{ "bioguideId": "S000033", "firstName": "Bernard", "lastName": "Sanders", "state": "Vermont",
+                 "partyHistory": [{ "partyName": "Independent", "startYear": 1991 }] }"#,
+        )
+        .unwrap();
+        store_member(&db, &Member::from(&detail), Some(&detail))
+            .await
+            .unwrap();
+
+        let stored: Option<Member> = db.select((MEMBER_TABLE, "S000033")).await.unwrap();
+        let stored = stored.unwrap();
+        assert_eq!(stored.name, "Sanders, Bernard");
+        assert_eq!(stored.party_name.as_deref(), Some("Independent"));
+    }
+}
@@ -1,12 +1,28 @@
 pub mod app;
 #[cfg(feature = "ssr")]
+pub mod config;
+#[cfg(feature = "ssr")]
+pub mod congress;
+#[cfg(feature = "ssr")]
 pub mod db;
 pub mod domain;
 pub mod error_template;
 #[cfg(feature = "ssr")]
 pub mod fileserv;
 #[cfg(feature = "ssr")]
+pub mod graph;
+#[cfg(feature = "ssr")]
+pub mod ingest;
+#[cfg(feature = "ssr")]
+pub mod migrations;
+#[cfg(feature = "ssr")]
+pub mod poller;
+#[cfg(feature = "ssr")]
+pub mod search;
+#[cfg(feature = "ssr")]
 pub mod state;
+#[cfg(feature = "ssr")]
+pub mod syndication;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
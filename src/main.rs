@@ -6,17 +6,22 @@ use http::Request;
 use leptos::logging::log;
 use leptos::provide_context;
 use leptos_axum::handle_server_fns_with_context;
-use surrealdb::engine::remote::ws::Ws;
-use surrealdb::Surreal;
 
 use congress_gov_surrealdb::state::AppState;
 
 #[cfg(feature = "ssr")]
 #[tokio::main]
-async fn main() -> surrealdb::Result<()> {
+async fn main() -> anyhow::Result<()> {
     use axum::Router;
     use congress_gov_surrealdb::app::*;
+    use congress_gov_surrealdb::config::Settings;
+    use congress_gov_surrealdb::congress::CongressClient;
+    use congress_gov_surrealdb::db;
     use congress_gov_surrealdb::fileserv::file_and_error_handler;
+    use congress_gov_surrealdb::ingest;
+    use congress_gov_surrealdb::migrations;
+    use congress_gov_surrealdb::poller;
+    use congress_gov_surrealdb::syndication;
     use leptos::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
 
@@ -30,24 +35,71 @@ async fn main() -> surrealdb::Result<()> {
     let addr = leptos_options.site_addr;
     let routes = generate_route_list(App);
 
+    // Load and validate settings before touching the database, `.env` is optional
+    dotenvy::dotenv().ok();
+    let settings = Settings::load()?;
+
     // Connect to SurrealDB
-    let db = Surreal::new::<Ws>("127.0.0.1:8000").await?;
+    logging::log!(
+        "connecting to SurrealDB at {} as {} ({}/{})",
+        settings.database.endpoint,
+        settings.database.auth,
+        settings.database.namespace,
+        settings.database.database
+    );
+    let db = db::connect(&settings.database).await?;
+
+    // Report the schema migrations and bring the database up to date
+    let report = migrations::status(&db).await?;
+    for entry in &report {
+        logging::log!("migration {entry}");
+    }
+    if settings.migrations.dry_run {
+        let pending = report
+            .iter()
+            .filter(|entry| matches!(entry.state, migrations::MigrationState::Pending))
+            .count();
+        logging::log!("dry run: {pending} migration(s) would be applied, exiting");
+        return Ok(());
+    }
+    if settings.migrations.auto_apply {
+        let applied = migrations::apply(&db).await?;
+        logging::log!("applied {} migration(s)", applied.len());
+    }
 
-    // Signin as a namespace, database, or root user
-    db.signin(surrealdb::opt::auth::Root {
-        username: "root",
-        password: "root",
-    })
-    .await?;
+    // Without an API key the app still runs, the bills pages say the key is missing
+    let congress = settings.congress.api_key().map(CongressClient::new);
+    if congress.is_none() {
+        logging::warn!(
+            "CONGRESS_GOV_API_TOKEN is not configured, Congress.gov data is unavailable"
+        );
+    }
 
-    // Select a specific namespace / database
-    db.use_ns("test").use_db("test").await?;
+    // Keep the local bill table in sync, pages read from it first
+    match &congress {
+        Some(client) if settings.ingest.enabled => {
+            ingest::spawn(db.clone(), client.clone(), settings.ingest.clone());
+        }
+        Some(_) => logging::log!("background bill sync is disabled"),
+        None => {}
+    }
+
+    // Poll the GovInfo RSS feeds, the feed pages only read what was stored
+    if settings.poller.enabled {
+        poller::spawn(db.clone(), settings.poller.clone());
+    } else {
+        logging::log!(
+            "background feed polling is disabled, feeds refresh when their page is opened"
+        );
+    }
 
     // Create app state
     let app_state = AppState {
         leptos_options,
         db: db.clone(),
         routes: routes.clone(),
+        settings,
+        congress,
     };
 
     // build our application with a route
@@ -56,6 +108,18 @@ async fn main() -> surrealdb::Result<()> {
             "/api/*fn_name",
             axum::routing::get(server_fn_handler).post(server_fn_handler),
         )
+        .route(
+            "/feeds/bills.xml",
+            axum::routing::get(syndication::bills_rss),
+        )
+        .route(
+            "/feeds/bills.atom",
+            axum::routing::get(syndication::bills_atom),
+        )
+        .route(
+            "/feeds/bills.json",
+            axum::routing::get(syndication::bills_json),
+        )
         .leptos_routes_with_handler(routes, axum::routing::get(leptos_routes_handler))
         .fallback(file_and_error_handler)
         .with_state(app_state);
@@ -79,6 +143,10 @@ async fn server_fn_handler(
     handle_server_fns_with_context(
         move || {
             provide_context(app_state.db.clone());
+            provide_context(app_state.settings.poller.clone());
+            if let Some(congress) = app_state.congress.clone() {
+                provide_context(congress);
+            }
         },
         request,
     )
@@ -90,6 +158,10 @@ async fn leptos_routes_handler(State(app_state): State<AppState>, req: Request<B
         app_state.routes.clone(),
         move || {
             provide_context(app_state.db.clone());
+            provide_context(app_state.settings.poller.clone());
+            if let Some(congress) = app_state.congress.clone() {
+                provide_context(congress);
+            }
         },
         App,
     );
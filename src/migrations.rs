@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use surrealdb::sql::Datetime;
+use surrealdb::{Connection, Surreal};
+use thiserror::Error;
+
+/// A versioned SurrealQL script from the `migrations` directory.
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+impl Migration {
+    pub fn checksum(&self) -> String {
+        format!("{:x}", Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+/// All migrations in the order they are applied. New scripts are appended with the next version.
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "person",
+        sql: include_str!("../migrations/0001_person.surql"),
+    },
+    Migration {
+        version: 2,
+        name: "feed_item",
+        sql: include_str!("../migrations/0002_feed_item.surql"),
+    },
+    Migration {
+        version: 3,
+        name: "bill",
+        sql: include_str!("../migrations/0003_bill.surql"),
+    },
+    Migration {
+        version: 4,
+        name: "feed_polling",
+        sql: include_str!("../migrations/0004_feed_polling.surql"),
+    },
+    Migration {
+        version: 5,
+        name: "bill_search",
+        sql: include_str!("../migrations/0005_bill_search.surql"),
+    },
+    Migration {
+        version: 6,
+        name: "bill_versions",
+        sql: include_str!("../migrations/0006_bill_versions.surql"),
+    },
+    Migration {
+        version: 7,
+        name: "member",
+        sql: include_str!("../migrations/0007_member.surql"),
+    },
+    Migration {
+        version: 8,
+        name: "sponsorship_edges",
+        sql: include_str!("../migrations/0008_sponsorship_edges.surql"),
+    },
+    Migration {
+        version: 9,
+        name: "collection_item",
+        sql: include_str!("../migrations/0009_collection_item.surql"),
+    },
+];
+
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("Migration query failed: {0}")]
+    Db(#[from] surrealdb::Error),
+    #[error("Migration {version} ({name}) was changed after it was applied")]
+    ChecksumMismatch { version: u32, name: String },
+    #[error("Migration {version} ({name}) is applied but unknown to this build")]
+    Unknown { version: u32, name: String },
+}
+
+/// A row of the `migration` bookkeeping table.
+#[derive(Debug, Deserialize)]
+struct AppliedMigration {
+    version: u32,
+    name: String,
+    checksum: String,
+    applied_at: Datetime,
+}
+
+#[derive(Debug)]
+pub enum MigrationState {
+    Applied {
+        applied_at: Datetime,
+    },
+    Pending,
+    /// The script changed since it was applied.
+    Modified,
+    /// Recorded in the database but not part of this build.
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: String,
+    pub state: MigrationState,
+}
+
+impl Display for MigrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04} {:<24} ", self.version, self.name)?;
+        match &self.state {
+            MigrationState::Applied { applied_at } => write!(f, "applied {applied_at}"),
+            MigrationState::Pending => write!(f, "pending"),
+            MigrationState::Modified => write!(f, "MODIFIED since it was applied"),
+            MigrationState::Unknown => write!(f, "UNKNOWN to this build"),
+        }
+    }
+}
+
+/// Compares the bundled migrations with the `migration` table without changing anything. Before
+/// the first migration there is no table, so everything is pending.
+pub async fn status<C: Connection>(
+    db: &Surreal<C>,
+) -> Result<Vec<MigrationStatus>, MigrationError> {
+    let applied: Vec<AppliedMigration> = if has_migration_table(db).await? {
+        db.query("SELECT version, name, checksum, applied_at FROM migration ORDER BY version")
+            .await?
+            .take(0)?
+    } else {
+        Vec::new()
+    };
+
+    let mut report: Vec<MigrationStatus> = MIGRATIONS
+        .iter()
+        .map(|migration| {
+            let state = match applied.iter().find(|a| a.version == migration.version) {
+                Some(a) if a.checksum != migration.checksum() => MigrationState::Modified,
+                Some(a) => MigrationState::Applied {
+                    applied_at: a.applied_at.clone(),
+                },
+                None => MigrationState::Pending,
+            };
+            MigrationStatus {
+                version: migration.version,
+                name: migration.name.to_string(),
+                state,
+            }
+        })
+        .collect();
+
+    report.extend(
+        applied
+            .into_iter()
+            .filter(|a| !MIGRATIONS.iter().any(|m| m.version == a.version))
+            .map(|a| MigrationStatus {
+                version: a.version,
+                name: a.name,
+                state: MigrationState::Unknown,
+            }),
+    );
+    report.sort_by_key(|s| s.version);
+
+    Ok(report)
+}
+
+async fn has_migration_table<C: Connection>(db: &Surreal<C>) -> Result<bool, surrealdb::Error> {
+    let tables: Option<BTreeMap<String, String>> =
+        db.query("INFO FOR DB").await?.take((0, "tables"))?;
+    Ok(tables.is_some_and(|tables| tables.contains_key("migration")))
+}
+
+/// Applies every pending migration in its own transaction and returns the versions that ran.
+///
+/// Refuses to run anything when an applied migration was modified or is unknown to this build.
+pub async fn apply<C: Connection>(db: &Surreal<C>) -> Result<Vec<u32>, MigrationError> {
+    let report = status(db).await?;
+
+    for entry in &report {
+        match entry.state {
+            MigrationState::Modified => {
+                return Err(MigrationError::ChecksumMismatch {
+                    version: entry.version,
+                    name: entry.name.clone(),
+                })
+            }
+            MigrationState::Unknown => {
+                return Err(MigrationError::Unknown {
+                    version: entry.version,
+                    name: entry.name.clone(),
+                })
+            }
+            _ => {}
+        }
+    }
+
+    let mut applied = Vec::new();
+    for entry in report
+        .iter()
+        .filter(|s| matches!(s.state, MigrationState::Pending))
+    {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.version == entry.version)
+            .expect("pending migrations come from MIGRATIONS");
+
+        let query = format!(
+            "BEGIN TRANSACTION;\n{}\nCREATE type::thing('migration', $version) CONTENT {{ version: $version, name: $name, checksum: $checksum, applied_at: time::now() }};\nCOMMIT TRANSACTION;",
+            migration.sql
+        );
+        db.query(query)
+            .bind(("version", migration.version))
+            .bind(("name", migration.name))
+            .bind(("checksum", migration.checksum()))
+            .await?
+            .check()?;
+
+        applied.push(migration.version);
+    }
+
+    Ok(applied)
+}
+
+/// A fresh in-memory database without any migrations, for tests.
+#[cfg(all(test, feature = "kv-mem"))]
+pub(crate) async fn empty_test_db() -> Surreal<crate::db::Engine> {
+    let db = surrealdb::engine::any::connect("mem://").await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    db
+}
+
+/// A fresh in-memory database with every migration applied, for tests.
+#[cfg(all(test, feature = "kv-mem"))]
+pub(crate) async fn test_db() -> Surreal<crate::db::Engine> {
+    let db = empty_test_db().await;
+    apply(&db).await.unwrap();
+    db
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_ordered() {
+        assert!(MIGRATIONS
+            .windows(2)
+            .all(|pair| pair[0].version < pair[1].version));
+    }
+
+    #[cfg(feature = "kv-mem")]
+    #[tokio::test]
+    async fn test_apply_is_idempotent() {
+        let db = empty_test_db().await;
+
+        // Reading the status of a fresh database doesn't create anything.
+        assert!(status(&db)
+            .await
+            .unwrap()
+            .iter()
+            .all(|s| matches!(s.state, MigrationState::Pending)));
+        assert!(!has_migration_table(&db).await.unwrap());
+
+        let first = apply(&db).await.unwrap();
+        let second = apply(&db).await.unwrap();
+
+        assert_eq!(first.len(), MIGRATIONS.len());
+        assert!(second.is_empty());
+        assert!(status(&db)
+            .await
+            .unwrap()
+            .iter()
+            .all(|s| matches!(s.state, MigrationState::Applied { .. })));
+    }
+}
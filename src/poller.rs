@@ -0,0 +1,348 @@
+//! Polls the GovInfo RSS feeds in the background with conditional GETs, honoring each feed's
+//! `<ttl>`, backing off after errors and recording every fetch in `feed_fetch`.
+
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use leptos::logging::warn;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use surrealdb::sql::Datetime;
+use surrealdb::{Connection, Surreal};
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::config::PollerSettings;
+use crate::domain::feeds::bills::parse_bill_item;
+use crate::domain::feeds::polling::{FeedFetch, FeedSource};
+use crate::domain::feeds::Collection;
+
+/// How often the poller checks which feeds are due.
+const TICK: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error)]
+pub enum PollError {
+    #[error("Request timed out: {0}")]
+    Timeout(reqwest::Error),
+    #[error("Request failed: {0}")]
+    Http(reqwest::Error),
+    #[error("Feed returned {0}")]
+    Status(StatusCode),
+    #[error("Could not read RSS: {0}")]
+    Rss(#[from] rss::Error),
+    #[error("Database error: {0}")]
+    Db(#[from] surrealdb::Error),
+}
+
+impl From<reqwest::Error> for PollError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            PollError::Timeout(error)
+        } else {
+            PollError::Http(error)
+        }
+    }
+}
+
+/// The HTTP client for feed requests. Every request gives up after `timeout_secs`, so a hanging
+/// feed fails the poll and backs off like any other error.
+pub fn client(settings: &PollerSettings) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(settings.timeout_secs))
+        .build()
+        .expect("the feed client only sets a timeout")
+}
+
+/// Polls every collection in the registry whenever it is due.
+pub fn spawn<C: Connection>(db: Surreal<C>, settings: PollerSettings) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let http = client(&settings);
+        let mut interval = tokio::time::interval(TICK);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            for collection in Collection::ALL {
+                if let Err(error) = poll_if_due(&db, &http, &settings, collection).await {
+                    warn!("polling the {} feed failed: {error}", collection.code());
+                }
+            }
+        }
+    })
+}
+
+/// Polls the feed while a page reads it if background polling is turned off, so the page still
+/// refreshes once the feed is due.
+pub async fn poll_on_request<C: Connection>(
+    db: &Surreal<C>,
+    settings: &PollerSettings,
+    collection: Collection,
+) -> Result<(), surrealdb::Error> {
+    if !settings.enabled {
+        poll_if_due(db, &client(settings), settings, collection).await?;
+    }
+    Ok(())
+}
+
+/// The stored state of a feed, or a fresh one that is due now.
+pub async fn source<C: Connection>(
+    db: &Surreal<C>,
+    collection: Collection,
+) -> Result<FeedSource, surrealdb::Error> {
+    let source: Option<FeedSource> = db.select(("feed_source", collection.slug())).await?;
+    Ok(source.unwrap_or_else(|| FeedSource::new(collection.rss_url())))
+}
+
+/// Polls the feed if its `next_poll` has passed. Fetch errors are recorded rather than returned.
+pub async fn poll_if_due<C: Connection>(
+    db: &Surreal<C>,
+    http: &reqwest::Client,
+    settings: &PollerSettings,
+    collection: Collection,
+) -> Result<Option<FeedFetch>, surrealdb::Error> {
+    let mut source = source(db, collection).await?;
+    if source.next_poll.0 > Utc::now() {
+        return Ok(None);
+    }
+
+    let started = Instant::now();
+    let mut fetch = FeedFetch {
+        source: collection.slug().to_string(),
+        url: source.url.clone(),
+        fetched_at: Datetime::from(Utc::now()),
+        status: None,
+        bytes: 0,
+        items: 0,
+        rejected: 0,
+        duration_ms: 0,
+        error: None,
+    };
+    match fetch_feed(db, http, collection, &mut source, &mut fetch).await {
+        Ok(()) => source.failures = 0,
+        Err(error) => {
+            source.failures += 1;
+            fetch.error = Some(error.to_string());
+        }
+    }
+    fetch.duration_ms = started.elapsed().as_millis() as u64;
+    source.next_poll = Datetime::from(Utc::now() + next_poll_delay(&source, settings));
+
+    db.query("UPDATE type::thing('feed_source', $slug) CONTENT $source RETURN NONE")
+        .query("CREATE feed_fetch CONTENT $fetch RETURN NONE")
+        .bind(("slug", collection.slug()))
+        .bind(("source", &source))
+        .bind(("fetch", &fetch))
+        .await?
+        .check()?;
+
+    Ok(Some(fetch))
+}
+
+/// Sends the conditional GET and stores what changed. Validators are only kept once the items are stored,
+/// so a failed run refetches the whole feed.
+async fn fetch_feed<C: Connection>(
+    db: &Surreal<C>,
+    http: &reqwest::Client,
+    collection: Collection,
+    source: &mut FeedSource,
+    fetch: &mut FeedFetch,
+) -> Result<(), PollError> {
+    let mut request = http.get(&source.url);
+    if let Some(etag) = &source.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &source.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    fetch.status = Some(status.as_u16());
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(());
+    }
+    if !status.is_success() {
+        return Err(PollError::Status(status));
+    }
+
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+    let body = response.bytes().await?;
+    fetch.bytes = body.len() as u64;
+
+    let channel = rss::Channel::read_from(&body[..])?;
+    source.ttl_minutes = channel.ttl().and_then(|ttl| ttl.trim().parse().ok());
+    let (items, rejected) = match collection {
+        Collection::Bills => store_bill_items(db, channel.into_items()).await?,
+        _ => store_collection_items(db, collection, channel.into_items()).await?,
+    };
+    fetch.items = items;
+    fetch.rejected = rejected;
+    source.etag = etag;
+    source.last_modified = last_modified;
+
+    Ok(())
+}
+
+/// The feed's `<ttl>` or the configured interval, whichever is longer. After failures the
+/// delay doubles from two minutes up to `max_backoff_minutes` instead.
+fn next_poll_delay(source: &FeedSource, settings: &PollerSettings) -> chrono::Duration {
+    let minutes = if source.failures == 0 {
+        settings
+            .interval_minutes
+            .max(source.ttl_minutes.unwrap_or_default())
+    } else {
+        (1u32 << source.failures.min(16)).min(settings.max_backoff_minutes)
+    };
+    chrono::Duration::minutes(minutes.max(1).into())
+}
+
+/// Upserts every readable item of the bills feed into `feed_item`. Returns the stored and rejected counts.
+async fn store_bill_items<C: Connection>(
+    db: &Surreal<C>,
+    items: Vec<rss::Item>,
+) -> Result<(u32, u32), surrealdb::Error> {
+    let (mut stored, mut rejected) = (0, 0);
+    for item in items {
+        let title = item.title.unwrap_or("No title found for Bill.".to_string());
+        let escaped_title = htmlize::unescape(title);
+        let bill = match parse_bill_item(escaped_title.as_ref(), item.link.as_deref()) {
+            Ok(bill) => bill,
+            Err(error) => {
+                warn!("skipping feed item: {error}");
+                rejected += 1;
+                continue;
+            }
+        };
+
+        let id = bill.id();
+        let version = bill
+            .bill_version
+            .as_ref()
+            .map(|version| version.code().to_string())
+            .unwrap_or_default();
+        let guid = item
+            .guid
+            .map(|guid| guid.value)
+            .or_else(|| item.link.clone())
+            .unwrap_or_else(|| escaped_title.to_string());
+
+        db.query(
+            "UPDATE type::thing('feed_item', [$key, $version, $guid]) SET \
+             bill = $bill, item = $item, guid = $guid, link = $link, \
+             first_seen = first_seen ?? time::now(), last_seen = time::now()",
+        )
+        .bind(("key", id.key()))
+        .bind(("version", version))
+        .bind(("guid", guid))
+        .bind(("bill", id.thing()))
+        .bind(("item", bill))
+        .bind(("link", item.link))
+        .await?
+        .check()?;
+        stored += 1;
+    }
+
+    Ok((stored, rejected))
+}
+
+/// Upserts every readable item of any other collection into `collection_item`. Returns the stored and
+/// rejected counts.
+async fn store_collection_items<C: Connection>(
+    db: &Surreal<C>,
+    collection: Collection,
+    items: Vec<rss::Item>,
+) -> Result<(u32, u32), surrealdb::Error> {
+    let (mut stored, mut rejected) = (0, 0);
+    for item in items {
+        let title = htmlize::unescape(item.title.unwrap_or_default());
+        let parsed = match collection.parse_item(title.as_ref(), item.link.as_deref()) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                warn!("skipping {} feed item: {error}", collection.code());
+                rejected += 1;
+                continue;
+            }
+        };
+
+        let guid = item
+            .guid
+            .map(|guid| guid.value)
+            .or_else(|| item.link.clone())
+            .unwrap_or_else(|| title.to_string());
+
+        db.query(
+            "UPDATE type::thing('collection_item', [$source, $guid]) SET \
+             source = $source, item = $item, guid = $guid, link = $link, \
+             first_seen = first_seen ?? time::now(), last_seen = time::now()",
+        )
+        .bind(("source", collection.slug()))
+        .bind(("guid", guid))
+        .bind(("item", parsed))
+        .bind(("link", item.link))
+        .await?
+        .check()?;
+        stored += 1;
+    }
+
+    Ok((stored, rejected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_poll_delay() {
+        let settings = PollerSettings::default();
+        let mut source = FeedSource::new(Collection::Bills.rss_url());
+
+        assert_eq!(next_poll_delay(&source, &settings).num_minutes(), 15);
+        source.ttl_minutes = Some(60);
+        assert_eq!(next_poll_delay(&source, &settings).num_minutes(), 60);
+
+        source.failures = 1;
+        assert_eq!(next_poll_delay(&source, &settings).num_minutes(), 2);
+        source.failures = 3;
+        assert_eq!(next_poll_delay(&source, &settings).num_minutes(), 8);
+        source.failures = 40;
+        assert_eq!(next_poll_delay(&source, &settings).num_minutes(), 360);
+    }
+
+    #[cfg(feature = "kv-mem")]
+    #[tokio::test]
+    async fn test_timeout_is_a_failed_poll() {
+        let db = crate::migrations::test_db().await;
+
+        // The connection is queued by the OS but nothing ever answers.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/plaw.xml", listener.local_addr().unwrap());
+        let _: Option<FeedSource> = db
+            .create(("feed_source", Collection::PublicLaws.slug()))
+            .content(FeedSource::new(url))
+            .await
+            .unwrap();
+
+        let settings = PollerSettings {
+            timeout_secs: 1,
+            ..PollerSettings::default()
+        };
+        let fetch = poll_if_due(&db, &client(&settings), &settings, Collection::PublicLaws)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetch.status, None);
+        assert!(fetch.error.unwrap().starts_with("Request timed out"));
+
+        let source = source(&db, Collection::PublicLaws).await.unwrap();
+        assert_eq!(source.failures, 1);
+        assert!(source.next_poll.0 > Utc::now());
+    }
+}
@@ -0,0 +1,117 @@
+//! Full-text search over the stored bills, using the indexes from `migrations/0005_bill_search.surql`.
+
+use surrealdb::{Connection, Surreal};
+
+use crate::domain::search::query::SearchQuery;
+use crate::domain::search::{SearchHit, MARK_END, MARK_START};
+
+/// Results shown for a search, best first.
+pub const SEARCH_LIMIT: usize = 50;
+
+/// Bills matching the query, see [`SearchQuery::to_surql`]. An empty query matches nothing.
+pub async fn search_bills<C: Connection>(
+    db: &Surreal<C>,
+    query: &SearchQuery,
+) -> Result<Vec<SearchHit>, surrealdb::Error> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    db.query(query.to_surql())
+        .bind(("text", &query.text))
+        .bind(("phrases", &query.phrases))
+        .bind(("congress", query.congress))
+        .bind(("type", query.bill_type))
+        .bind((
+            "sponsor",
+            query.sponsor.as_ref().map(|sponsor| sponsor.to_lowercase()),
+        ))
+        .bind(("stage", query.stage))
+        .bind(("version", &query.version))
+        .bind(("mark_start", MARK_START.to_string()))
+        .bind(("mark_end", MARK_END.to_string()))
+        .bind(("limit", SEARCH_LIMIT))
+        .await?
+        .take(0)
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_search_bills() {
+        use crate::domain::bills::{BillListItem, BillType, LegislativeStage};
+        use crate::domain::search::fragments;
+        use crate::ingest::store_bill;
+
+        let db = crate::migrations::test_db().await;
+
+        for (number, title, summary, stage) in [
+            (
+                "1",
+                "Clean Energy Jobs Act",
+                "Funds solar training.",
+                "reported",
+            ),
+            (
+                "2",
+                "Arts Education Act",
+                "Grants for clean water in schools.",
+                "introduced",
+            ),
+            ("3", "Farm Bill", "Crop insurance.", "introduced"),
+        ] {
+            let bill: BillListItem = serde_json::from_value(serde_json::json!({
+                "congress": 118, "number": number, "title": title, "type": "HR"
+            }))
+            .unwrap();
+            let id = bill.id().unwrap();
+            store_bill(&db, &id, &bill, None).await.unwrap();
+            db.query("UPDATE $id SET summary = $summary, stage = $stage")
+                .bind(("id", id.thing()))
+                .bind(("summary", summary))
+                .bind(("stage", stage))
+                .await
+                .unwrap()
+                .check()
+                .unwrap();
+        }
+
+        let query = SearchQuery {
+            text: "clean".to_string(),
+            ..Default::default()
+        };
+        let hits = search_bills(&db, &query).await.unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(fragments(&hits[0].title)
+            .iter()
+            .any(|fragment| fragment.matched));
+
+        // Quoted words must appear together and in order.
+        let query: SearchQuery = "\"clean water\"".parse().unwrap();
+        let hits = search_bills(&db, &query).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].number, "2");
+        let query: SearchQuery = "\"water clean\"".parse().unwrap();
+        assert!(search_bills(&db, &query).await.unwrap().is_empty());
+
+        let mut query = SearchQuery {
+            text: "clean".to_string(),
+            ..Default::default()
+        };
+        query.stage = Some(LegislativeStage::Reported);
+        let hits = search_bills(&db, &query).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].number, "1");
+        assert_eq!(hits[0].bill_type, BillType::HR);
+
+        // Filters alone list bills too.
+        let query: SearchQuery = "type:hr stage:introduced".parse().unwrap();
+        assert_eq!(search_bills(&db, &query).await.unwrap().len(), 2);
+        assert!(search_bills(&db, &SearchQuery::default())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
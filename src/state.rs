@@ -1,11 +1,37 @@
 use axum::extract::FromRef;
 use leptos::LeptosOptions;
 use leptos_router::RouteListing;
-use surrealdb::{engine::remote::ws::Client, Surreal};
+use surrealdb::{Connection, Surreal};
 
-#[derive(FromRef, Debug, Clone)]
-pub struct AppState {
+use crate::config::Settings;
+use crate::congress::CongressClient;
+use crate::db::Engine;
+
+#[derive(Debug, Clone)]
+pub struct AppState<C: Connection = Engine> {
     pub leptos_options: LeptosOptions,
-    pub db: Surreal<Client>,
+    pub db: Surreal<C>,
     pub routes: Vec<RouteListing>,
+    pub settings: Settings,
+    /// `None` when no Congress.gov API key is configured, pages then show a notice instead.
+    pub congress: Option<CongressClient>,
+}
+
+// `#[derive(FromRef)]` doesn't support generics, so the substates handlers extract are listed here.
+impl<C: Connection> FromRef<AppState<C>> for LeptosOptions {
+    fn from_ref(state: &AppState<C>) -> Self {
+        state.leptos_options.clone()
+    }
+}
+
+impl<C: Connection> FromRef<AppState<C>> for Settings {
+    fn from_ref(state: &AppState<C>) -> Self {
+        state.settings.clone()
+    }
+}
+
+impl<C: Connection> FromRef<AppState<C>> for Surreal<C> {
+    fn from_ref(state: &AppState<C>) -> Self {
+        state.db.clone()
+    }
 }
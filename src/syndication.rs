@@ -0,0 +1,493 @@
+//! Our own RSS, Atom and JSON Feed versions of the stored bill feed, at `/feeds/bills.xml`,
+//! `/feeds/bills.atom` and `/feeds/bills.json`. Query parameters narrow the feed down:
+//! `?type=hr&version=enr&congress=118&party=D`.
+
+use axum::extract::{Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Datetime;
+use surrealdb::Surreal;
+
+use crate::config::Settings;
+use crate::db::Engine;
+use crate::domain::bills::{BillTextVersionAbbreviation, BillType};
+use crate::domain::feeds::bills::BillItem;
+
+/// Entries per feed document, newest first.
+const FEED_LIMIT: usize = 100;
+const FEED_TITLE: &str = "Congress.gov bills";
+
+/// Filters taken from the query string. Every filter is optional and they combine with AND.
+#[derive(Debug, Default, Deserialize)]
+pub struct FeedQuery {
+    #[serde(rename = "type")]
+    pub bill_type: Option<String>,
+    pub version: Option<String>,
+    pub congress: Option<u32>,
+    /// Party of the sponsor, e.g. `D` or `republican`. Only matches bills whose detail was synced.
+    pub party: Option<String>,
+}
+
+/// A stored feed item with what the feed documents show besides the item itself.
+#[derive(Debug, Deserialize)]
+struct Entry {
+    item: BillItem,
+    guid: String,
+    first_seen: Datetime,
+    party: Option<String>,
+}
+
+impl Entry {
+    fn title(&self) -> String {
+        let id = self.item.id();
+        match &self.item.bill_version {
+            Some(version) => format!(
+                "{} {} ({}): {}",
+                id.bill_type.citation(),
+                id.number,
+                version.code(),
+                self.item.title
+            ),
+            None => format!(
+                "{} {}: {}",
+                id.bill_type.citation(),
+                id.number,
+                self.item.title
+            ),
+        }
+    }
+
+    fn summary(&self) -> String {
+        let mut summary = self.item.id().to_string();
+        if let Some(version) = &self.item.bill_version {
+            summary.push_str(&format!(", {version}"));
+        }
+        if let Some(party) = &self.party {
+            summary.push_str(&format!(", sponsored by a member of party {party}"));
+        }
+        summary
+    }
+
+    /// Filter values that apply to the entry, as RSS and Atom categories and JSON Feed tags.
+    fn categories(&self) -> Vec<String> {
+        let mut categories = vec![
+            self.item.bill_type.to_string(),
+            self.item.congress.to_string(),
+        ];
+        if let Some(version) = &self.item.bill_version {
+            categories.push(version.code().to_string());
+        }
+        categories.extend(self.party.clone());
+        categories
+    }
+}
+
+pub async fn bills_rss(
+    State(db): State<Surreal<Engine>>,
+    State(settings): State<Settings>,
+    Query(query): Query<FeedQuery>,
+) -> Response {
+    match load_entries(&db, &query).await {
+        Ok(entries) => {
+            let body = rss_document(settings.site.base_url(), &entries);
+            (
+                [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+                body,
+            )
+                .into_response()
+        }
+        Err(error) => error.into_response(),
+    }
+}
+
+pub async fn bills_atom(
+    State(db): State<Surreal<Engine>>,
+    State(settings): State<Settings>,
+    Query(query): Query<FeedQuery>,
+) -> Response {
+    match load_entries(&db, &query).await {
+        Ok(entries) => {
+            let body = atom_document(settings.site.base_url(), &entries);
+            (
+                [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+                body,
+            )
+                .into_response()
+        }
+        Err(error) => error.into_response(),
+    }
+}
+
+pub async fn bills_json(
+    State(db): State<Surreal<Engine>>,
+    State(settings): State<Settings>,
+    Query(query): Query<FeedQuery>,
+) -> Response {
+    match load_entries(&db, &query).await {
+        Ok(entries) => {
+            let body = json_feed(settings.site.base_url(), &entries);
+            (
+                [(header::CONTENT_TYPE, "application/feed+json")],
+                axum::Json(body),
+            )
+                .into_response()
+        }
+        Err(error) => error.into_response(),
+    }
+}
+
+#[derive(Debug)]
+enum FeedError {
+    InvalidFilter(String),
+    Db(surrealdb::Error),
+}
+
+impl IntoResponse for FeedError {
+    fn into_response(self) -> Response {
+        match self {
+            FeedError::InvalidFilter(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+            FeedError::Db(error) => {
+                leptos::logging::warn!("bill feed query failed: {error}");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+/// The query string filters parsed into the values the feed query binds.
+#[derive(Debug, PartialEq)]
+struct Filters {
+    bill_type: Option<BillType>,
+    version: Option<BillTextVersionAbbreviation>,
+    /// Initial of the party, as stored in the bill detail.
+    party: Option<String>,
+}
+
+impl FeedQuery {
+    /// Rejects a type or version that doesn't exist rather than serving an empty feed.
+    fn filters(&self) -> Result<Filters, FeedError> {
+        let bill_type = self
+            .bill_type
+            .as_deref()
+            .map(|bill_type| bill_type.parse::<BillType>())
+            .transpose()
+            .map_err(|error| FeedError::InvalidFilter(error.to_string()))?;
+        let version = self
+            .version
+            .as_deref()
+            .map(|code| {
+                BillTextVersionAbbreviation::from_code(code).ok_or_else(|| {
+                    FeedError::InvalidFilter(format!("'{code}' is not a text version code"))
+                })
+            })
+            .transpose()?;
+        let party = self
+            .party
+            .as_deref()
+            .and_then(|party| party.trim().chars().next())
+            .map(|initial| initial.to_ascii_uppercase().to_string());
+
+        Ok(Filters {
+            bill_type,
+            version,
+            party,
+        })
+    }
+}
+
+async fn load_entries(db: &Surreal<Engine>, query: &FeedQuery) -> Result<Vec<Entry>, FeedError> {
+    let Filters {
+        bill_type,
+        version,
+        party,
+    } = query.filters()?;
+
+    db.query(
+        "SELECT item, guid, first_seen, bill.detail.sponsors[0].party AS party FROM feed_item \
+         WHERE ($type = NONE OR item.bill_type = $type) \
+         AND ($version = NONE OR item.bill_version = $version) \
+         AND ($congress = NONE OR item.congress = $congress) \
+         AND ($party = NONE OR bill.detail.sponsors[0].party = $party) \
+         ORDER BY first_seen DESC LIMIT $limit",
+    )
+    .bind(("type", bill_type))
+    .bind(("version", version))
+    .bind(("congress", query.congress))
+    .bind(("party", party))
+    .bind(("limit", FEED_LIMIT))
+    .await
+    .and_then(|mut response| response.take(0))
+    .map_err(FeedError::Db)
+}
+
+fn rss_document(site: &str, entries: &[Entry]) -> String {
+    use rss::{CategoryBuilder, ChannelBuilder, GuidBuilder, ItemBuilder};
+
+    let items = entries
+        .iter()
+        .map(|entry| {
+            ItemBuilder::default()
+                .title(entry.title())
+                .link(format!("{site}{}", entry.item.id().url()))
+                .description(entry.summary())
+                .guid(
+                    GuidBuilder::default()
+                        .value(entry.guid.clone())
+                        .permalink(false)
+                        .build(),
+                )
+                .pub_date(entry.first_seen.0.to_rfc2822())
+                .categories(
+                    entry
+                        .categories()
+                        .into_iter()
+                        .map(|name| CategoryBuilder::default().name(name).build())
+                        .collect::<Vec<_>>(),
+                )
+                .build()
+        })
+        .collect::<Vec<_>>();
+
+    ChannelBuilder::default()
+        .title(FEED_TITLE)
+        .link(format!("{site}/feeds/bills"))
+        .description("Bill versions published by GovInfo, as seen by this site.")
+        .items(items)
+        .build()
+        .to_string()
+}
+
+fn atom_document(site: &str, entries: &[Entry]) -> String {
+    use htmlize::{escape_attribute, escape_text};
+    use std::fmt::Write;
+
+    let updated = entries
+        .first()
+        .map(|entry| entry.first_seen.0)
+        .unwrap_or_else(chrono::Utc::now);
+    let mut document = String::new();
+    let _ = write!(
+        document,
+        r#"<?xml version="1.0" encoding="utf-8"?><feed xmlns="http://www.w3.org/2005/Atom"><title>{}</title><id>{}</id><link rel="self" href="{}"/><link href="{}"/><updated>{}</updated>"#,
+        escape_text(FEED_TITLE),
+        escape_text(format!("{site}/feeds/bills.atom")),
+        escape_attribute(format!("{site}/feeds/bills.atom")),
+        escape_attribute(format!("{site}/feeds/bills")),
+        updated.to_rfc3339(),
+    );
+    for entry in entries {
+        let link = format!("{site}{}", entry.item.id().url());
+        let _ = write!(
+            document,
+            r#"<entry><title>{}</title><id>urn:govinfo:{}</id><link href="{}"/><updated>{}</updated><summary>{}</summary>"#,
+            escape_text(entry.title()),
+            escape_text(entry.guid.as_str()),
+            escape_attribute(link),
+            entry.first_seen.0.to_rfc3339(),
+            escape_text(entry.summary()),
+        );
+        for category in entry.categories() {
+            let _ = write!(
+                document,
+                r#"<category term="{}"/>"#,
+                escape_attribute(category)
+            );
+        }
+        document.push_str("</entry>");
+    }
+    document.push_str("</feed>");
+    document
+}
+
+/// JSON Feed 1.1, <https://www.jsonfeed.org/version/1.1/>.
+#[derive(Debug, Serialize)]
+struct JsonFeed {
+    version: &'static str,
+    title: &'static str,
+    home_page_url: String,
+    feed_url: String,
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonFeedItem {
+    id: String,
+    url: String,
+    title: String,
+    content_text: String,
+    date_published: String,
+    tags: Vec<String>,
+}
+
+fn json_feed(site: &str, entries: &[Entry]) -> JsonFeed {
+    JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: FEED_TITLE,
+        home_page_url: format!("{site}/feeds/bills"),
+        feed_url: format!("{site}/feeds/bills.json"),
+        items: entries
+            .iter()
+            .map(|entry| JsonFeedItem {
+                id: entry.guid.clone(),
+                url: format!("{site}{}", entry.item.id().url()),
+                title: entry.title(),
+                content_text: entry.summary(),
+                date_published: entry.first_seen.0.to_rfc3339(),
+                tags: entry.categories(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str, guid: &str) -> Entry {
+        Entry {
+            item: BillItem {
+                congress: 118,
+                bill_type: BillType::HR,
+                bill_number: 7261,
+                bill_version: Some(BillTextVersionAbbreviation::IH),
+                title: title.to_string(),
+            },
+            guid: guid.to_string(),
+            first_seen: Datetime::from(chrono::Utc::now()),
+            party: Some("D".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_documents_escape_xml() {
+        let entries = [entry(
+            "Tariffs on <steel> & \"aluminum\"",
+            "BILLS-118hr7261ih&x",
+        )];
+
+        let atom = atom_document("https://example.org/a&b", &entries);
+        assert!(atom.contains("<id>https://example.org/a&amp;b/feeds/bills.atom</id>"));
+        assert!(atom.contains("Tariffs on &lt;steel&gt; &amp;"));
+        assert!(atom.contains("<id>urn:govinfo:BILLS-118hr7261ih&amp;x</id>"));
+        assert!(!atom.contains("<steel>"));
+
+        let rss = rss_document("https://example.org", &entries);
+        assert!(rss.contains("Tariffs on &lt;steel&gt; &amp;"));
+        assert!(rss.contains("<link>https://example.org/bills/118/hr/7261</link>"));
+        assert!(!rss.contains("<steel>"));
+    }
+
+    #[test]
+    fn test_invalid_filters_are_bad_requests() {
+        for query in [
+            FeedQuery {
+                bill_type: Some("bogus".to_string()),
+                ..Default::default()
+            },
+            FeedQuery {
+                version: Some("xyz".to_string()),
+                ..Default::default()
+            },
+        ] {
+            let error = query.filters().unwrap_err();
+            assert!(matches!(error, FeedError::InvalidFilter(_)));
+            assert_eq!(error.into_response().status(), StatusCode::BAD_REQUEST);
+        }
+
+        let query = FeedQuery {
+            bill_type: Some("hr".to_string()),
+            version: Some("enr".to_string()),
+            congress: Some(118),
+            party: Some(" democrat".to_string()),
+        };
+        assert_eq!(
+            query.filters().unwrap(),
+            Filters {
+                bill_type: Some(BillType::HR),
+                version: Some(BillTextVersionAbbreviation::ENR),
+                party: Some("D".to_string()),
+            }
+        );
+    }
+
+    #[cfg(feature = "kv-mem")]
+    #[tokio::test]
+    async fn test_load_filtered_entries() {
+        let db = crate::migrations::test_db().await;
+
+        for (bill_type, number, version, party) in [
+            (BillType::HR, 1, BillTextVersionAbbreviation::IH, "D"),
+            (BillType::HR, 2, BillTextVersionAbbreviation::ENR, "R"),
+            (BillType::S, 3, BillTextVersionAbbreviation::ENR, "D"),
+        ] {
+            let item = BillItem {
+                congress: 118,
+                bill_type,
+                bill_number: number,
+                bill_version: Some(version),
+                title: format!("Bill {number}"),
+            };
+            let id = item.id();
+            db.query(
+                "UPDATE $bill SET congress = 118, type = $type, number = $number, title = $item.title, \
+                 synced_at = time::now(), detail = { sponsors: [{ party: $party }] }",
+            )
+            .query(
+                "CREATE feed_item SET bill = $bill, item = $item, guid = $guid, link = NONE, \
+                 first_seen = time::now(), last_seen = time::now()",
+            )
+            .bind(("bill", id.thing()))
+            .bind(("type", bill_type))
+            .bind(("number", number.to_string()))
+            .bind(("party", party))
+            .bind(("item", &item))
+            .bind(("guid", id.key()))
+            .await
+            .unwrap()
+            .check()
+            .unwrap();
+        }
+
+        let guids = |query: FeedQuery| {
+            let db = db.clone();
+            async move {
+                let mut guids: Vec<String> = load_entries(&db, &query)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|entry| entry.guid)
+                    .collect();
+                guids.sort();
+                guids
+            }
+        };
+        assert_eq!(guids(FeedQuery::default()).await.len(), 3);
+        assert_eq!(
+            guids(FeedQuery {
+                version: Some("enr".to_string()),
+                ..Default::default()
+            })
+            .await,
+            ["118-hr-2", "118-s-3"]
+        );
+        assert_eq!(
+            guids(FeedQuery {
+                version: Some("enr".to_string()),
+                party: Some("democrat".to_string()),
+                ..Default::default()
+            })
+            .await,
+            ["118-s-3"]
+        );
+        assert_eq!(
+            guids(FeedQuery {
+                bill_type: Some("hr".to_string()),
+                congress: Some(117),
+                ..Default::default()
+            })
+            .await,
+            Vec::<String>::new()
+        );
+    }
+}